@@ -0,0 +1,58 @@
+// Embeds the git commit the server was built from, shown by `/healthz` and `/readyz`.
+// Builds without a checkout (e.g. Docker, where `.git` is ignored) can pass it
+// through the `GIT_COMMIT` environment variable instead.
+//
+// Also embeds the example programs under `examples/`, served by `/api/v1/examples`.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn main() {
+    embed_git_commit();
+    embed_examples();
+}
+
+fn embed_git_commit() {
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    
+    let commit = std::env::var("GIT_COMMIT")
+        .ok()
+        .filter(|commit| !commit.is_empty())
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+                .map(|commit| commit.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_COMMIT={}", commit);
+}
+
+/// Writes `$OUT_DIR/examples.rs` with the name and contents of every `.src`
+/// file under `examples/`, sorted by file name
+fn embed_examples() {
+    println!("cargo:rerun-if-changed=examples");
+    
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("cannot read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("readable directory entry").path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "src"))
+        .collect();
+    files.sort();
+    
+    let mut code = String::from("pub static EXAMPLE_FILES: &[(&str, &str)] = &[\n");
+    for path in &files {
+        let name = path.file_name().unwrap().to_string_lossy();
+        code.push_str(&format!("    ({:?}, include_str!({:?})),\n", name, path.display().to_string()));
+    }
+    code.push_str("];\n");
+    
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(&out, code).unwrap_or_else(|e| panic!("cannot write {}: {}", out.display(), e));
+}
@@ -1,10 +1,14 @@
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+// Children refer back to ASTNode; stop the schema walk from recursing into them forever
+#[cfg_attr(feature = "openapi", schema(no_recursion))]
 pub enum ASTNode {
     // Literals
     Number { value: String, is_float: bool },
     String { value: String },
+    Char { value: char },
     Boolean { value: bool },
     Identifier { name: String },
     
@@ -27,12 +31,68 @@ pub enum ASTNode {
         right: Box<ASTNode>,
     },
     
-    // Function call
+    // Parallel assignment (`a, b = b, a`); every value is evaluated before any target is assigned
+    MultipleAssignment {
+        targets: Vec<ASTNode>,
+        values: Vec<ASTNode>,
+    },
+    
+    // Increment/decrement statement (`x++`, `x--`)
+    IncDec {
+        target: Box<ASTNode>,
+        operator: String,
+    },
+    
+    // Compound assignment statement (`x += 1`, `mask <<= 2`)
+    CompoundAssignment {
+        target: Box<ASTNode>,
+        operator: String,
+        value: Box<ASTNode>,
+    },
+    
+    // Function or method call; the callee is any expression (`f`, `s.upper`, `fns[0]`)
     FunctionCall {
-        name: String,
+        callee: Box<ASTNode>,
         arguments: Vec<ASTNode>,
     },
     
+    // Composite literal (`[]int{1, 2}`, `[3]string{}`); `literal_type` is the type as written, lengths in decimal
+    CompositeLiteral {
+        literal_type: String,
+        elements: Vec<ASTNode>,
+    },
+    
+    // Index expression (`a[i]`)
+    Index {
+        target: Box<ASTNode>,
+        index: Box<ASTNode>,
+    },
+    
+    // Slice expression (`a[lo:hi]`); either bound may be omitted
+    Slice {
+        target: Box<ASTNode>,
+        low: Option<Box<ASTNode>>,
+        high: Option<Box<ASTNode>>,
+    },
+    
+    // Keyed element of a composite literal (`X: 1`)
+    KeyValue {
+        key: Box<ASTNode>,
+        value: Box<ASTNode>,
+    },
+    
+    // Field access (`p.X`)
+    FieldAccess {
+        target: Box<ASTNode>,
+        field: String,
+    },
+    
+    // Struct type declaration (`type Point struct { X int; Y float64 }`)
+    TypeDeclaration {
+        name: String,
+        fields: Vec<StructField>,
+    },
+    
     // Parenthesized expression
     Parenthesized {
         expression: Box<ASTNode>,
@@ -49,17 +109,35 @@ pub enum ASTNode {
     },
 }
 
+/// One field of a struct type declaration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StructField {
+    pub name: String,
+    pub field_type: String,
+}
+
 impl ASTNode {
     pub fn node_type(&self) -> &'static str {
         match self {
             ASTNode::Number { .. } => "Number",
             ASTNode::String { .. } => "String",
+            ASTNode::Char { .. } => "Char",
             ASTNode::Boolean { .. } => "Boolean",
             ASTNode::Identifier { .. } => "Identifier",
             ASTNode::BinaryOp { .. } => "BinaryOp",
             ASTNode::UnaryOp { .. } => "UnaryOp",
             ASTNode::Assignment { .. } => "Assignment",
+            ASTNode::MultipleAssignment { .. } => "MultipleAssignment",
+            ASTNode::IncDec { .. } => "IncDec",
+            ASTNode::CompoundAssignment { .. } => "CompoundAssignment",
             ASTNode::FunctionCall { .. } => "FunctionCall",
+            ASTNode::CompositeLiteral { .. } => "CompositeLiteral",
+            ASTNode::Index { .. } => "Index",
+            ASTNode::Slice { .. } => "Slice",
+            ASTNode::KeyValue { .. } => "KeyValue",
+            ASTNode::FieldAccess { .. } => "FieldAccess",
+            ASTNode::TypeDeclaration { .. } => "TypeDeclaration",
             ASTNode::Parenthesized { .. } => "Parenthesized",
             ASTNode::Program { .. } => "Program",
             ASTNode::ExpressionStatement { .. } => "ExpressionStatement",
@@ -72,12 +150,29 @@ impl ASTNode {
                 format!("Number\n{} ({})", value, if *is_float { "float" } else { "int" })
             },
             ASTNode::String { value } => format!("String\n\"{}\"", value),
+            ASTNode::Char { value } => format!("Char\n'{}'", value.escape_default()),
             ASTNode::Boolean { value } => format!("Boolean\n{}", value),
             ASTNode::Identifier { name } => format!("Identifier\n{}", name),
             ASTNode::BinaryOp { operator, .. } => format!("BinaryOp\n{}", operator),
             ASTNode::UnaryOp { operator, .. } => format!("UnaryOp\n{}", operator),
             ASTNode::Assignment { .. } => "Assignment\n=".to_string(),
-            ASTNode::FunctionCall { name, .. } => format!("FunctionCall\n{}", name),
+            ASTNode::MultipleAssignment { .. } => "MultipleAssignment\n=".to_string(),
+            ASTNode::IncDec { operator, .. } => format!("IncDec\n{}", operator),
+            ASTNode::CompoundAssignment { operator, .. } => format!("CompoundAssignment\n{}", operator),
+            ASTNode::FunctionCall { callee, .. } => match &**callee {
+                ASTNode::Identifier { name } => format!("FunctionCall\n{}", name),
+                ASTNode::FieldAccess { field, .. } => format!("FunctionCall\n.{}", field),
+                _ => "FunctionCall".to_string(),
+            },
+            ASTNode::CompositeLiteral { literal_type, .. } => format!("CompositeLiteral\n{}", literal_type),
+            ASTNode::Index { .. } => "Index\n[ ]".to_string(),
+            ASTNode::Slice { .. } => "Slice\n[:]".to_string(),
+            ASTNode::KeyValue { .. } => "KeyValue\n:".to_string(),
+            ASTNode::FieldAccess { field, .. } => format!("FieldAccess\n.{}", field),
+            ASTNode::TypeDeclaration { name, fields } => {
+                let fields: Vec<String> = fields.iter().map(|f| format!("{} {}", f.name, f.field_type)).collect();
+                format!("TypeDeclaration\n{}\n{}", name, fields.join("\n"))
+            },
             ASTNode::Parenthesized { .. } => "Parenthesized\n( )".to_string(),
             ASTNode::Program { .. } => "Program".to_string(),
             ASTNode::ExpressionStatement { .. } => "ExpressionStatement".to_string(),
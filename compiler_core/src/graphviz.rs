@@ -29,7 +29,7 @@ impl GraphvizRenderer {
         dot.push_str("digraph AST {\n");
         dot.push_str("  node [shape=rectangle, style=\"rounded,filled\", fillcolor=lightblue];\n");
         dot.push_str("  rankdir=TB;\n");
-        dot.push_str("\n");
+        dot.push('\n');
         
         self.render_node(ast, &mut dot);
         
@@ -77,12 +77,66 @@ impl GraphvizRenderer {
                 dot.push_str(&format!("  node_{} -> node_{} [label=\"left\"];\n", node_id, left_id));
                 dot.push_str(&format!("  node_{} -> node_{} [label=\"right\"];\n", node_id, right_id));
             },
-            ASTNode::FunctionCall { name: _, arguments } => {
+            ASTNode::MultipleAssignment { targets, values } => {
+                for (i, target) in targets.iter().enumerate() {
+                    let target_id = self.render_node(target, dot);
+                    dot.push_str(&format!("  node_{} -> node_{} [label=\"target{}\"];\n", node_id, target_id, i));
+                }
+                for (i, value) in values.iter().enumerate() {
+                    let value_id = self.render_node(value, dot);
+                    dot.push_str(&format!("  node_{} -> node_{} [label=\"value{}\"];\n", node_id, value_id, i));
+                }
+            },
+            ASTNode::IncDec { target, operator: _ } => {
+                let target_id = self.render_node(target, dot);
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"target\"];\n", node_id, target_id));
+            },
+            ASTNode::CompoundAssignment { target, operator: _, value } => {
+                let target_id = self.render_node(target, dot);
+                let value_id = self.render_node(value, dot);
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"target\"];\n", node_id, target_id));
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"value\"];\n", node_id, value_id));
+            },
+            ASTNode::FunctionCall { callee, arguments } => {
+                let callee_id = self.render_node(callee, dot);
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"callee\"];\n", node_id, callee_id));
                 for (i, arg) in arguments.iter().enumerate() {
                     let arg_id = self.render_node(arg, dot);
                     dot.push_str(&format!("  node_{} -> node_{} [label=\"arg{}\"];\n", node_id, arg_id, i));
                 }
             },
+            ASTNode::CompositeLiteral { literal_type: _, elements } => {
+                for (i, element) in elements.iter().enumerate() {
+                    let element_id = self.render_node(element, dot);
+                    dot.push_str(&format!("  node_{} -> node_{} [label=\"elem{}\"];\n", node_id, element_id, i));
+                }
+            },
+            ASTNode::Index { target, index } => {
+                let target_id = self.render_node(target, dot);
+                let index_id = self.render_node(index, dot);
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"target\"];\n", node_id, target_id));
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"index\"];\n", node_id, index_id));
+            },
+            ASTNode::Slice { target, low, high } => {
+                let target_id = self.render_node(target, dot);
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"target\"];\n", node_id, target_id));
+                for (bound, edge) in [(low, "low"), (high, "high")] {
+                    if let Some(bound) = bound {
+                        let bound_id = self.render_node(bound, dot);
+                        dot.push_str(&format!("  node_{} -> node_{} [label=\"{}\"];\n", node_id, bound_id, edge));
+                    }
+                }
+            },
+            ASTNode::KeyValue { key, value } => {
+                let key_id = self.render_node(key, dot);
+                let value_id = self.render_node(value, dot);
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"key\"];\n", node_id, key_id));
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"value\"];\n", node_id, value_id));
+            },
+            ASTNode::FieldAccess { target, field: _ } => {
+                let target_id = self.render_node(target, dot);
+                dot.push_str(&format!("  node_{} -> node_{} [label=\"target\"];\n", node_id, target_id));
+            },
             ASTNode::Parenthesized { expression } => {
                 let expr_id = self.render_node(expression, dot);
                 dot.push_str(&format!("  node_{} -> node_{} [label=\"expr\"];\n", node_id, expr_id));
@@ -98,7 +152,9 @@ impl GraphvizRenderer {
                 dot.push_str(&format!("  node_{} -> node_{} [label=\"expr\"];\n", node_id, expr_id));
             },
             // Leaf nodes (literals, identifiers) don't have children
-            ASTNode::Number { .. } | ASTNode::String { .. } | ASTNode::Boolean { .. } | ASTNode::Identifier { .. } => {},
+            ASTNode::Number { .. } | ASTNode::String { .. } | ASTNode::Char { .. } | ASTNode::Boolean { .. } | ASTNode::Identifier { .. } => {},
+            // Struct fields are part of the declaration's label
+            ASTNode::TypeDeclaration { .. } => {},
         }
         
         node_id
@@ -109,12 +165,22 @@ impl GraphvizRenderer {
         match node {
             ASTNode::Number { .. } => (label, "lightgreen"),
             ASTNode::String { .. } => (label, "lightyellow"),
+            ASTNode::Char { .. } => (label, "khaki"),
             ASTNode::Boolean { .. } => (label, "lightblue"),
             ASTNode::Identifier { .. } => (label, "lightcyan"),
             ASTNode::BinaryOp { .. } => (label, "lightcoral"),
             ASTNode::UnaryOp { .. } => (label, "lightpink"),
             ASTNode::Assignment { .. } => (label, "orange"),
+            ASTNode::MultipleAssignment { .. } => (label, "darkorange"),
+            ASTNode::IncDec { .. } => (label, "peachpuff"),
+            ASTNode::CompoundAssignment { .. } => (label, "sandybrown"),
             ASTNode::FunctionCall { .. } => (label, "lightsteelblue"),
+            ASTNode::CompositeLiteral { .. } => (label, "palegreen"),
+            ASTNode::Index { .. } => (label, "thistle"),
+            ASTNode::Slice { .. } => (label, "plum"),
+            ASTNode::KeyValue { .. } => (label, "honeydew"),
+            ASTNode::FieldAccess { .. } => (label, "mistyrose"),
+            ASTNode::TypeDeclaration { .. } => (label, "lightgoldenrod"),
             ASTNode::Parenthesized { .. } => (label, "lavender"),
             ASTNode::Program { .. } => (label, "lightgray"),
             ASTNode::ExpressionStatement { .. } => (label, "wheat"),
@@ -147,7 +213,7 @@ pub fn render_ast_to_png(ast: &ASTNode, base_filename: &str) -> Result<(), Box<d
     
     // Try to generate PNG using dot command
     match std::process::Command::new("dot")
-        .args(&["-Tpng", &dot_filename, "-o", &png_filename])
+        .args(["-Tpng", &dot_filename, "-o", &png_filename])
         .output() {
         Ok(output) => {
             if output.status.success() {
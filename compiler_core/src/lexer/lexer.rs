@@ -3,6 +3,7 @@
 extern crate thiserror;
 
 use std::collections::HashMap;
+use std::fmt;
 use std::io;
 use std::iter::Peekable;
 use std::str::Chars;
@@ -26,13 +27,61 @@ pub enum LexerError {
     },
 
     #[error("Invalid numeric symbol: {raw:?}")]
-    InvalidNumeric { raw: String },
+    InvalidNumeric { raw: String, line: usize, col: usize },
 
     #[error("Unmatched opening symbol {open:?} for closing symbol {symbol:?}")]
-    MissbalancedSymbols { symbol: char, open: char },
+    MissbalancedSymbols { symbol: char, open: char, line: usize, col: usize },
 
     #[error("Unknown symbol: {symbol}")]
-    UnknownSymbol { symbol: String },
+    UnknownSymbol { symbol: String, line: usize, col: usize },
+
+    #[error("Unterminated string literal starting at line {line}, column {col}")]
+    UnterminatedString { line: usize, col: usize },
+
+    #[error("Invalid escape sequence {escape:?} at line {line}, column {col}: {reason}")]
+    InvalidEscape { escape: String, reason: String, line: usize, col: usize },
+
+    #[error("Invalid rune literal at line {line}, column {col}: {reason}")]
+    InvalidRune { reason: String, line: usize, col: usize },
+
+    #[error("Unterminated block comment starting at line {line}, column {col}")]
+    UnterminatedComment { line: usize, col: usize },
+
+    #[error("Nested block comment at line {line}, column {col}; block comments cannot be nested")]
+    NestedBlockComment { line: usize, col: usize },
+}
+
+impl LexerError {
+    /// Stable, machine-readable name of the error variant
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LexerError::FileIOError(_) => "io_error",
+            LexerError::MissingExpectedSymbol { .. } => "missing_expected_symbol",
+            LexerError::InvalidNumeric { .. } => "invalid_numeric",
+            LexerError::MissbalancedSymbols { .. } => "unbalanced_symbols",
+            LexerError::UnknownSymbol { .. } => "unknown_symbol",
+            LexerError::UnterminatedString { .. } => "unterminated_string",
+            LexerError::InvalidEscape { .. } => "invalid_escape",
+            LexerError::InvalidRune { .. } => "invalid_rune",
+            LexerError::UnterminatedComment { .. } => "unterminated_comment",
+            LexerError::NestedBlockComment { .. } => "nested_block_comment",
+        }
+    }
+    
+    /// Line and column the error points at, for the variants that record one
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            LexerError::InvalidNumeric { line, col, .. }
+            | LexerError::MissbalancedSymbols { line, col, .. }
+            | LexerError::UnknownSymbol { line, col, .. }
+            | LexerError::UnterminatedString { line, col }
+            | LexerError::InvalidEscape { line, col, .. }
+            | LexerError::InvalidRune { line, col, .. }
+            | LexerError::UnterminatedComment { line, col }
+            | LexerError::NestedBlockComment { line, col } => Some((*line, *col)),
+            _ => None,
+        }
+    }
 }
 
 // =====================
@@ -47,12 +96,65 @@ pub struct Punctuation {
     pub kind: PunctuationKind,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum NumericHint {
     Integer,
     Float,
 }
 
+/// Decoded value of a numeric literal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NumericValue {
+    Integer(u64),
+    /// Integer literal that does not fit in 64 bits
+    BigInteger,
+    Float(f64),
+}
+
+impl NumericValue {
+    /// Decodes the source text of a literal the lexer accepted, honouring
+    /// `0x`/`0o`/`0b` prefixes, Go's legacy octal (`0123`) and `_` separators
+    pub fn from_literal(raw: &str, is_float: bool) -> Option<NumericValue> {
+        let clean: String = raw.chars().filter(|c| *c != '_').collect();
+        if is_float {
+            return clean.parse().ok().map(NumericValue::Float);
+        }
+        let (digits, radix) = match clean.get(..2) {
+            Some("0x" | "0X") => (&clean[2..], 16),
+            Some("0o" | "0O") => (&clean[2..], 8),
+            Some("0b" | "0B") => (&clean[2..], 2),
+            _ if clean.len() > 1 && clean.starts_with('0') => (&clean[1..], 8),
+            _ => (clean.as_str(), 10),
+        };
+        match u64::from_str_radix(digits, radix) {
+            Ok(value) => Some(NumericValue::Integer(value)),
+            Err(e) if *e.kind() == std::num::IntErrorKind::PosOverflow => Some(NumericValue::BigInteger),
+            Err(_) => None,
+        }
+    }
+}
+
+/// Maps the letter after a leading `0` to the radix it selects
+fn radix_for_prefix(c: char) -> Option<u32> {
+    match c {
+        'x' | 'X' => Some(16),
+        'o' | 'O' => Some(8),
+        'b' | 'B' => Some(2),
+        _ => None,
+    }
+}
+
+/// Checks that every `_` sits between two digits (or right after a base prefix)
+fn separators_are_valid(digits: &str, radix: u32, allow_leading: bool) -> bool {
+    let chars: Vec<char> = digits.chars().collect();
+    chars.iter().enumerate().all(|(i, &c)| {
+        let after_digit = if i == 0 { allow_leading } else { chars[i - 1].is_digit(radix) };
+        let before_digit = chars.get(i + 1).is_some_and(|next| next.is_digit(radix));
+        c != '_' || (after_digit && before_digit)
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Numeric {
     pub raw: String,
@@ -65,13 +167,169 @@ pub enum TokenType {
     Punctuation { raw: char, kind: PunctuationKind },
     Operator(String),
     Identificador(String),
+    Keyword(Keyword),
     Char(char),
-    Numero { raw: String, kind: NumericHint },
+    Numero { raw: String, kind: NumericHint, value: NumericValue },
     Cadena(String),
     Boolean(bool),
     Unknown(String),
 }
 
+/// Stable classification of a token for API consumers, independent of how
+/// `TokenType` is laid out in Rust
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum TokenCategory {
+    Punctuation,
+    Operator,
+    Identifier,
+    Keyword,
+    Rune,
+    Number,
+    String,
+    Boolean,
+    Unknown,
+    EOF,
+}
+
+impl TokenType {
+    /// Returns the token's category
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            TokenType::EOF => TokenCategory::EOF,
+            TokenType::Punctuation { .. } => TokenCategory::Punctuation,
+            TokenType::Operator(_) => TokenCategory::Operator,
+            TokenType::Identificador(_) => TokenCategory::Identifier,
+            TokenType::Keyword(_) => TokenCategory::Keyword,
+            TokenType::Char(_) => TokenCategory::Rune,
+            TokenType::Numero { .. } => TokenCategory::Number,
+            TokenType::Cadena(_) => TokenCategory::String,
+            TokenType::Boolean(_) => TokenCategory::Boolean,
+            TokenType::Unknown(_) => TokenCategory::Unknown,
+        }
+    }
+
+    /// Returns whether a numeric literal is an integer or a float
+    pub fn numeric_kind(&self) -> Option<NumericHint> {
+        match self {
+            TokenType::Numero { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+
+    /// Returns the nesting depth of an opening or closing bracket
+    pub fn bracket_depth(&self) -> Option<BalancingDepthType> {
+        match self {
+            TokenType::Punctuation { kind: PunctuationKind::Open(depth) | PunctuationKind::Close(depth), .. } => Some(*depth),
+            _ => None,
+        }
+    }
+}
+
+/// Reserved words of the language; they can never be used as identifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Keyword {
+    Break,
+    Case,
+    Chan,
+    Const,
+    Continue,
+    Default,
+    Defer,
+    Else,
+    Fallthrough,
+    For,
+    Func,
+    Go,
+    Goto,
+    If,
+    Import,
+    Interface,
+    Map,
+    Package,
+    Range,
+    Return,
+    Select,
+    Struct,
+    Switch,
+    Type,
+    Var,
+}
+
+/// Central keyword table, used both to recognize and to print keywords
+pub const KEYWORDS: &[(&str, Keyword)] = &[
+    ("break", Keyword::Break),
+    ("case", Keyword::Case),
+    ("chan", Keyword::Chan),
+    ("const", Keyword::Const),
+    ("continue", Keyword::Continue),
+    ("default", Keyword::Default),
+    ("defer", Keyword::Defer),
+    ("else", Keyword::Else),
+    ("fallthrough", Keyword::Fallthrough),
+    ("for", Keyword::For),
+    ("func", Keyword::Func),
+    ("go", Keyword::Go),
+    ("goto", Keyword::Goto),
+    ("if", Keyword::If),
+    ("import", Keyword::Import),
+    ("interface", Keyword::Interface),
+    ("map", Keyword::Map),
+    ("package", Keyword::Package),
+    ("range", Keyword::Range),
+    ("return", Keyword::Return),
+    ("select", Keyword::Select),
+    ("struct", Keyword::Struct),
+    ("switch", Keyword::Switch),
+    ("type", Keyword::Type),
+    ("var", Keyword::Var),
+];
+
+impl Keyword {
+    /// Looks up a word in the keyword table
+    pub fn lookup(word: &str) -> Option<Keyword> {
+        KEYWORDS.iter().find(|(text, _)| *text == word).map(|(_, keyword)| *keyword)
+    }
+
+    /// Returns the source spelling of the keyword
+    pub fn as_str(&self) -> &'static str {
+        KEYWORDS
+            .iter()
+            .find(|(_, keyword)| keyword == self)
+            .map(|(text, _)| *text)
+            .expect("every keyword is listed in KEYWORDS")
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Source text between tokens, kept when the lexer is built with `keep_trivia`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum Trivia {
+    Whitespace(String),
+    LineComment(String),
+    BlockComment(String),
+}
+
+/// A token together with its source text and the trivia that precedes it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriviaToken {
+    pub token: TokenType,
+    /// The token exactly as written in the source, e.g. `0x1F` or `"a\n"`
+    pub lexeme: String,
+    pub leading_trivia: Vec<Trivia>,
+    /// Line of the token's first character, starting at 1
+    pub line: usize,
+    /// Column of the token's first character, starting at 1
+    pub column: usize,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PunctuationKind {
     Open(BalancingDepthType),
@@ -79,19 +337,26 @@ pub enum PunctuationKind {
     Separator,
 }
 
-type BalancingDepthType = i32;
+pub type BalancingDepthType = i32;
 type CharIter<'a> = Peekable<Chars<'a>>;
 
 // =====================
 // Lexer Implementation
 // =====================
 
+#[derive(Clone)]
 pub struct Lexer<'a> {
     pub cur_line: usize,
     pub cur_col: usize,
     pub position_offset: usize,
+    input: &'a str,
+    byte_offset: usize,
     chars: CharIter<'a>,
     balancing_state: HashMap<char, BalancingDepthType>,
+    keep_trivia: bool,
+    trivia: Vec<Trivia>,
+    /// Line and column where the token being lexed starts
+    token_start: (usize, usize),
 }
 
 impl<'a> Lexer<'a> {
@@ -101,21 +366,44 @@ impl<'a> Lexer<'a> {
             cur_line: 1,
             cur_col: 0,
             position_offset: 0,
+            input,
+            byte_offset: 0,
             chars: input.chars().peekable(),
             balancing_state: HashMap::new(),
+            keep_trivia: false,
+            trivia: Vec::new(),
+            token_start: (1, 1),
         }
     }
 
+    /// Keeps whitespace and comments as trivia on the following token
+    pub fn keep_trivia(mut self, keep: bool) -> Lexer<'a> {
+        self.keep_trivia = keep;
+        self
+    }
+
     /// Maps an opening or closing symbol to its matching pair
-    fn map_balanced_state(c: &char) -> char {
+    fn map_balanced_state(&self, c: &char) -> Result<char, LexerError> {
         match c {
-            '(' => ')', ')' => '(',
-            '{' => '}', '}' => '{',
-            '[' => ']', ']' => '[',
-            _ => panic!("Unexpected character for balancing state: {}", c),
+            '(' => Ok(')'), ')' => Ok('('),
+            '{' => Ok('}'), '}' => Ok('{'),
+            '[' => Ok(']'), ']' => Ok('['),
+            _ => Err(self.unknown_symbol(*c)),
         }
     }
 
+    /// Error for a character that starts no token
+    fn unknown_symbol(&self, c: char) -> LexerError {
+        let (line, col) = self.token_start;
+        LexerError::UnknownSymbol { symbol: c.to_string(), line, col }
+    }
+
+    /// Error for a malformed numeric literal; it points at the literal's first digit
+    fn invalid_numeric(&self, raw: String) -> LexerError {
+        let (line, col) = self.token_start;
+        LexerError::InvalidNumeric { raw, line, col }
+    }
+
     /// Pushes an opening symbol and increases its depth
     fn push_open(&mut self, c: &char) -> BalancingDepthType {
         let entry = self.balancing_state.entry(*c).or_insert(0);
@@ -126,33 +414,45 @@ impl<'a> Lexer<'a> {
 
     /// Pops a closing symbol and validates it against expected opening symbol
     fn pop_close(&mut self, c: &char) -> Result<BalancingDepthType, LexerError> {
-        let open = Self::map_balanced_state(c);
+        let open = self.map_balanced_state(c)?;
         match self.balancing_state.get_mut(&open) {
             Some(depth) if *depth > 0 => {
                 *depth -= 1;
                 Ok(*depth)
             }
-            _ => Err(LexerError::MissbalancedSymbols { symbol: *c, open }),
+            _ => {
+                let (line, col) = self.token_start;
+                Err(LexerError::MissbalancedSymbols { symbol: *c, open, line, col })
+            }
         }
     }
 
     /// Consumes one digit and validates it
     fn consume_digit(&mut self, raw: &str) -> Result<char, LexerError> {
-        match self.chars.next() {
-            Some(c) if c.is_ascii_digit() => Ok(c),
-            Some(_) | None => Err(LexerError::InvalidNumeric { raw: raw.to_string() }),
+        match self.chars.peek() {
+            Some(c) if c.is_ascii_digit() => Ok(self.consume_char().unwrap()),
+            Some(_) | None => Err(self.invalid_numeric(raw.to_string())),
         }
     }
 
-    /// Parses a numeric literal, including integers and floats with optional exponent
+    /// Parses a numeric literal: decimal integers and floats with optional exponent,
+    /// `0x`/`0o`/`0b` prefixed integers, octal integers with a leading `0`, and `_` digit separators
     fn parse_number(&mut self, c: char) -> Result<TokenType, LexerError> {
+        let mut number = c.to_string();
+
+        if c == '0' {
+            if let Some(radix) = self.chars.peek().and_then(|p| radix_for_prefix(*p)) {
+                number.push(self.consume_char().unwrap());
+                return self.parse_prefixed_integer(number, radix);
+            }
+        }
+
         let mut seen_dot = false;
         let mut seen_e = false;
-        let mut number = c.to_string();
 
         while let Some(&next) = self.chars.peek() {
             match next {
-                d if d.is_ascii_digit() => number.push(self.consume_char().unwrap()),
+                d if d.is_ascii_digit() || d == '_' => number.push(self.consume_char().unwrap()),
                 '.' if !seen_dot && !seen_e => {
                     seen_dot = true;
                     number.push(self.consume_char().unwrap());
@@ -163,49 +463,162 @@ impl<'a> Lexer<'a> {
                     if matches!(self.chars.peek(), Some('+' | '-')) {
                         number.push(self.consume_char().unwrap());
                     }
-                    self.consume_digit(&number)?;
+                    number.push(self.consume_digit(&number)?);
                 }
                 a if a.is_alphabetic() => {
                     number.push(self.consume_char().unwrap());
-                    return Err(LexerError::InvalidNumeric { raw: number });
+                    return Err(self.invalid_numeric(number));
                 }
                 _ => break,
             }
         }
 
-        Ok(TokenType::Numero {
-            raw: number,
-            kind: if seen_dot || seen_e {
-                NumericHint::Float
-            } else {
-                NumericHint::Integer
-            },
-        })
+        if !separators_are_valid(&number, 10, false) {
+            return Err(self.invalid_numeric(number));
+        }
+        let kind = if seen_dot || seen_e {
+            NumericHint::Float
+        } else {
+            NumericHint::Integer
+        };
+        // As in Go, an integer with a leading zero is octal, so `09` is not a number
+        if kind == NumericHint::Integer && number.starts_with('0') && !number.chars().all(|d| d.is_digit(8) || d == '_') {
+            return Err(self.invalid_numeric(number));
+        }
+        self.numeric_token(number, kind)
     }
 
-    /// Parses a string literal with support for escape sequences
+    /// Parses the digits of a `0x`, `0o` or `0b` literal after its prefix
+    fn parse_prefixed_integer(&mut self, mut number: String, radix: u32) -> Result<TokenType, LexerError> {
+        while let Some(&next) = self.chars.peek() {
+            match next {
+                d if d.is_digit(radix) || d == '_' => number.push(self.consume_char().unwrap()),
+                a if a.is_alphanumeric() => {
+                    number.push(self.consume_char().unwrap());
+                    return Err(self.invalid_numeric(number));
+                }
+                _ => break,
+            }
+        }
+
+        let digits = &number[2..];
+        if !digits.chars().any(|d| d.is_digit(radix)) || !separators_are_valid(digits, radix, true) {
+            return Err(self.invalid_numeric(number));
+        }
+        self.numeric_token(number, NumericHint::Integer)
+    }
+
+    /// Builds a numeric token, decoding its value from the raw text
+    fn numeric_token(&self, raw: String, kind: NumericHint) -> Result<TokenType, LexerError> {
+        match NumericValue::from_literal(&raw, kind == NumericHint::Float) {
+            Some(value) => Ok(TokenType::Numero { raw, kind, value }),
+            None => Err(self.invalid_numeric(raw)),
+        }
+    }
+
+    /// Parses an interpreted string literal, decoding its escape sequences
     fn parse_string(&mut self, _c: char) -> Result<TokenType, LexerError> {
+        let (line, col) = (self.cur_line, self.cur_col);
         let mut string = String::new();
 
-        while let Some(c) = self.chars.next() {
+        while let Some(c) = self.consume_char() {
             match c {
                 '"' => return Ok(TokenType::Cadena(string)),
-                '\\' => {
-                    if let Some(escaped) = self.chars.next() {
-                        string.push(escaped);
-                    } else {
-                        return Err(LexerError::UnknownSymbol {
-                            symbol: "Unterminated escape sequence".to_string(),
-                        });
-                    }
-                }
+                '\\' => string.push(self.parse_escape('"')?),
                 other => string.push(other),
             }
         }
 
-        Err(LexerError::UnknownSymbol {
-            symbol: "Unterminated string literal".to_string(),
-        })
+        Err(LexerError::UnterminatedString { line, col })
+    }
+
+    /// Parses a raw string literal between backticks; its contents are taken verbatim
+    fn parse_raw_string(&mut self) -> Result<TokenType, LexerError> {
+        let (line, col) = (self.cur_line, self.cur_col);
+        let mut string = String::new();
+
+        while let Some(c) = self.consume_char() {
+            match c {
+                '`' => return Ok(TokenType::Cadena(string)),
+                // Carriage returns are discarded from raw strings, as in Go
+                '\r' => {}
+                other => string.push(other),
+            }
+        }
+
+        Err(LexerError::UnterminatedString { line, col })
+    }
+
+    /// Parses a rune literal holding exactly one character, such as 'a' or '\n'
+    fn parse_rune(&mut self) -> Result<TokenType, LexerError> {
+        let (line, col) = (self.cur_line, self.cur_col);
+        let invalid = |reason: &str| LexerError::InvalidRune { reason: reason.to_string(), line, col };
+
+        let value = match self.consume_char() {
+            Some('\\') => self.parse_escape('\'')?,
+            Some('\'') => return Err(invalid("empty rune literal")),
+            Some('\n') | None => return Err(invalid("unterminated rune literal")),
+            Some(c) => c,
+        };
+        match self.consume_char() {
+            Some('\'') => Ok(TokenType::Char(value)),
+            _ => Err(invalid("rune literal must contain exactly one character")),
+        }
+    }
+
+    /// Decodes the escape sequence after a backslash. `quote` is the delimiter
+    /// of the enclosing literal, the only quote that may be escaped there.
+    fn parse_escape(&mut self, quote: char) -> Result<char, LexerError> {
+        let (line, col) = (self.cur_line, self.cur_col);
+        let mut escape = String::from('\\');
+        let invalid = |escape: String, reason: &str| LexerError::InvalidEscape {
+            escape,
+            reason: reason.to_string(),
+            line,
+            col,
+        };
+
+        let Some(c) = self.consume_char() else {
+            return Err(invalid(escape, "unterminated escape sequence"));
+        };
+        escape.push(c);
+        let (digits, radix) = match c {
+            'a' => return Ok('\u{07}'),
+            'b' => return Ok('\u{08}'),
+            'f' => return Ok('\u{0C}'),
+            'n' => return Ok('\n'),
+            'r' => return Ok('\r'),
+            't' => return Ok('\t'),
+            'v' => return Ok('\u{0B}'),
+            '\\' => return Ok('\\'),
+            q if q == quote => return Ok(q),
+            'x' => (2, 16),
+            'u' => (4, 16),
+            'U' => (8, 16),
+            // Octal escapes have three digits, the first of which is `c`
+            '0'..='7' => (2, 8),
+            _ => return Err(invalid(escape, "unknown escape sequence")),
+        };
+
+        for _ in 0..digits {
+            match self.chars.peek() {
+                Some(d) if d.is_digit(radix) => escape.push(self.consume_char().unwrap()),
+                _ => {
+                    let expected = match radix {
+                        8 => "expected three octal digits",
+                        _ => "expected more hexadecimal digits",
+                    };
+                    return Err(invalid(escape, expected));
+                }
+            }
+        }
+
+        let value_digits = if radix == 8 { &escape[1..] } else { &escape[2..] };
+        let value = u32::from_str_radix(value_digits, radix).unwrap();
+        if matches!(c, 'x' | '0'..='7') && value > 0x7F {
+            return Err(invalid(escape, "byte escapes above \\x7f are not valid UTF-8 text; use \\u instead"));
+        }
+        char::from_u32(value).ok_or_else(|| invalid(escape, "not a valid Unicode code point"))
     }
 
     /// Maps a character to its corresponding token type
@@ -219,22 +632,31 @@ impl<'a> Lexer<'a> {
                 raw: c,
                 kind: PunctuationKind::Close(self.pop_close(&c)?),
             }),
-            ',' | ';' => Ok(TokenType::Punctuation {
+            ',' | ';' | ':' | '.' => Ok(TokenType::Punctuation {
                 raw: c,
                 kind: PunctuationKind::Separator,
             }),
             '0'..='9' => self.parse_number(c),
             '"' => self.parse_string(c),
-            '+' | '-' | '*' | '/' | '=' | '<' | '>' | '!' | '&' | '|' => {
+            '`' => self.parse_raw_string(),
+            '\'' => self.parse_rune(),
+            '+' | '-' | '*' | '/' | '%' | '=' | '<' | '>' | '!' | '&' | '|' | '^' => {
                 let mut operator = c.to_string();
                 if let Some(&next) = self.chars.peek() {
                     if matches!((c, next), 
                         ('=', '=') | ('!', '=') | ('<', '=') | ('>', '=') |
-                        ('&', '&') | ('|', '|') | ('+', '+') | ('-', '-')) 
+                        ('&', '&') | ('|', '|') | ('+', '+') | ('-', '-') |
+                        ('<', '<') | ('>', '>') | ('&', '^') |
+                        ('+', '=') | ('-', '=') | ('*', '=') | ('/', '=') | ('%', '=') |
+                        ('&', '=') | ('|', '=') | ('^', '=')) 
                     {
                         operator.push(self.consume_char().unwrap());
                     }
                 }
+                // `<<=`, `>>=` and `&^=`
+                if matches!(operator.as_str(), "<<" | ">>" | "&^") && self.chars.peek() == Some(&'=') {
+                    operator.push(self.consume_char().unwrap());
+                }
                 Ok(TokenType::Operator(operator))
             }
             a if a.is_alphabetic() || a == '_' => {
@@ -245,40 +667,118 @@ impl<'a> Lexer<'a> {
                 match ident.as_str() {
                     "true" => Ok(TokenType::Boolean(true)),
                     "false" => Ok(TokenType::Boolean(false)),
-                    _ => Ok(TokenType::Identificador(ident)),
+                    _ => match Keyword::lookup(&ident) {
+                        Some(keyword) => Ok(TokenType::Keyword(keyword)),
+                        None => Ok(TokenType::Identificador(ident)),
+                    },
                 }
             }
-            _ => Err(LexerError::UnknownSymbol { symbol: c.to_string() }),
+            _ => Err(self.unknown_symbol(c)),
         }
     }
 
     /// Returns the next token in the stream
     pub fn next_token(&mut self) -> Result<TokenType, LexerError> {
-        self.skip_whitespace();
-        match self.consume_char() {
-            Some(c) => self.transform_to_type(c),
-            None => Ok(TokenType::EOF),
-        }
+        self.next_token_with_trivia().map(|t| t.token)
+    }
+
+    /// Returns the next token along with the whitespace and comments before it
+    pub fn next_token_with_trivia(&mut self) -> Result<TriviaToken, LexerError> {
+        self.skip_trivia()?;
+        let leading_trivia = std::mem::take(&mut self.trivia);
+        let start = self.byte_offset;
+        let (line, column) = (self.cur_line, self.cur_col + 1);
+        self.token_start = (line, column);
+        let token = match self.consume_char() {
+            Some(c) => self.transform_to_type(c)?,
+            None => TokenType::EOF,
+        };
+        let lexeme = self.input[start..self.byte_offset].to_string();
+        Ok(TriviaToken { token, lexeme, leading_trivia, line, column })
+    }
+
+    /// Returns the next token without advancing the lexer
+    pub fn peek_token(&self) -> Result<TokenType, LexerError> {
+        self.clone().next_token()
     }
 
     /// Consumes a character and updates cursor position
     pub fn consume_char(&mut self) -> Option<char> {
-        self.chars.next().map(|c| {
+        self.chars.next().inspect(|&c| {
             self.position_offset += 1;
+            self.byte_offset += c.len_utf8();
             if c == '\n' {
                 self.cur_line += 1;
-                self.cur_col = 1;
+                self.cur_col = 0;
             } else {
                 self.cur_col += 1;
             }
-            c
         })
     }
 
-    /// Skips all whitespace characters
-    fn skip_whitespace(&mut self) {
-        while matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
-            self.consume_char();
+    /// Returns the character after the next one without consuming anything
+    fn peek_second(&self) -> Option<char> {
+        let mut ahead = self.chars.clone();
+        ahead.next();
+        ahead.next()
+    }
+
+    /// Skips whitespace and comments, recording them as trivia if requested
+    fn skip_trivia(&mut self) -> Result<(), LexerError> {
+        loop {
+            match (self.chars.peek().copied(), self.peek_second()) {
+                (Some(c), _) if c.is_whitespace() => {
+                    let mut text = String::new();
+                    while matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
+                        text.push(self.consume_char().unwrap());
+                    }
+                    self.push_trivia(Trivia::Whitespace(text));
+                }
+                (Some('/'), Some('/')) => {
+                    let mut text = String::new();
+                    while matches!(self.chars.peek(), Some(c) if *c != '\n') {
+                        text.push(self.consume_char().unwrap());
+                    }
+                    self.push_trivia(Trivia::LineComment(text));
+                }
+                (Some('/'), Some('*')) => {
+                    let text = self.block_comment()?;
+                    self.push_trivia(Trivia::BlockComment(text));
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Consumes a `/* ... */` comment, rejecting nested openers
+    fn block_comment(&mut self) -> Result<String, LexerError> {
+        let (line, col) = (self.cur_line, self.cur_col + 1);
+        let mut text = String::new();
+        text.push(self.consume_char().unwrap());
+        text.push(self.consume_char().unwrap());
+
+        loop {
+            match (self.chars.peek().copied(), self.peek_second()) {
+                (Some('*'), Some('/')) => {
+                    text.push(self.consume_char().unwrap());
+                    text.push(self.consume_char().unwrap());
+                    return Ok(text);
+                }
+                (Some('/'), Some('*')) => {
+                    return Err(LexerError::NestedBlockComment {
+                        line: self.cur_line,
+                        col: self.cur_col + 1,
+                    });
+                }
+                (Some(_), _) => text.push(self.consume_char().unwrap()),
+                (None, _) => return Err(LexerError::UnterminatedComment { line, col }),
+            }
+        }
+    }
+
+    fn push_trivia(&mut self, trivia: Trivia) {
+        if self.keep_trivia {
+            self.trivia.push(trivia);
         }
     }
 
@@ -294,6 +794,20 @@ impl<'a> Lexer<'a> {
         }
         Ok(tokens)
     }
+
+    /// Tokenizes the full input keeping trivia, including the final EOF token
+    pub fn tokenize_all_with_trivia(&mut self) -> Result<Vec<TriviaToken>, LexerError> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.next_token_with_trivia()?;
+            let at_end = matches!(token.token, TokenType::EOF);
+            tokens.push(token);
+            if at_end {
+                break;
+            }
+        }
+        Ok(tokens)
+    }
 }
 
 // =====================
@@ -309,14 +823,50 @@ mod tests {
         let mut lexer = Lexer::new("123");
         let token = lexer.next_token().unwrap();
         match token {
-            TokenType::Numero { raw, kind } => {
+            TokenType::Numero { raw, kind, value } => {
                 assert_eq!(raw, "123");
                 assert_eq!(kind, NumericHint::Integer);
+                assert_eq!(value, NumericValue::Integer(123));
             }
             _ => panic!("Expected number token"),
         }
     }
 
+    #[test]
+    fn test_prefixed_numbers_and_separators() {
+        let cases = [
+            ("0x1F", NumericValue::Integer(31)),
+            ("0o17", NumericValue::Integer(15)),
+            ("0B1010", NumericValue::Integer(10)),
+            ("1_000_000", NumericValue::Integer(1_000_000)),
+            ("0x_ff_ff", NumericValue::Integer(0xffff)),
+            ("0", NumericValue::Integer(0)),
+            ("0123", NumericValue::Integer(0o123)),
+            ("0_17", NumericValue::Integer(0o17)),
+            ("09.5", NumericValue::Float(9.5)),
+            ("0e1", NumericValue::Float(0.0)),
+            ("1_0.2_5e1_0", NumericValue::Float(10.25e10)),
+            ("99999999999999999999", NumericValue::BigInteger),
+        ];
+        for (source, expected) in cases {
+            match Lexer::new(source).next_token().unwrap() {
+                TokenType::Numero { raw, value, .. } => {
+                    assert_eq!(raw, source);
+                    assert_eq!(value, expected, "{}", source);
+                }
+                other => panic!("Expected number token for {}, got {:?}", source, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_numbers() {
+        for source in ["0x", "0xG1", "0b102", "1__0", "1_", "0x1_", "1_.5", "1e", "09", "0128", "0_9"] {
+            let err = Lexer::new(source).tokenize_all().unwrap_err();
+            assert!(matches!(err, LexerError::InvalidNumeric { .. }), "{}: {:?}", source, err);
+        }
+    }
+
     #[test]
     fn test_boolean_true() {
         let mut lexer = Lexer::new("true");
@@ -324,6 +874,25 @@ mod tests {
         assert_eq!(token, TokenType::Boolean(true));
     }
 
+    #[test]
+    fn test_keywords() {
+        let mut lexer = Lexer::new("if iffy return");
+        let tokens = lexer.tokenize_all().unwrap();
+        assert_eq!(tokens, vec![
+            TokenType::Keyword(Keyword::If),
+            TokenType::Identificador("iffy".to_string()),
+            TokenType::Keyword(Keyword::Return),
+        ]);
+    }
+
+    #[test]
+    fn test_keyword_table_round_trips() {
+        for (text, keyword) in KEYWORDS {
+            assert_eq!(Keyword::lookup(text), Some(*keyword));
+            assert_eq!(keyword.as_str(), *text);
+        }
+    }
+
     #[test]
     fn test_string_literal() {
         let mut lexer = Lexer::new("\"hello\"");
@@ -331,6 +900,44 @@ mod tests {
         assert_eq!(token, TokenType::Cadena("hello".to_string()));
     }
 
+    #[test]
+    fn test_string_escapes() {
+        let mut lexer = Lexer::new(r#""tab\there \"q\" \\ \x41\u00e9\U0001F600\101""#);
+        let token = lexer.next_token().unwrap();
+        assert_eq!(token, TokenType::Cadena("tab\there \"q\" \\ Aé😀A".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_escape_points_at_backslash() {
+        let mut lexer = Lexer::new("s = \"ok\n  \\q\"");
+        match lexer.tokenize_all().unwrap_err() {
+            LexerError::InvalidEscape { escape, line, col, .. } => {
+                assert_eq!(escape, "\\q");
+                assert_eq!((line, col), (2, 3));
+            }
+            other => panic!("Expected invalid escape, got {:?}", other),
+        }
+        for source in [r#""\x4""#, r#""\xff""#, r#""\uD800""#, r#""\'""#] {
+            let err = Lexer::new(source).tokenize_all().unwrap_err();
+            assert!(matches!(err, LexerError::InvalidEscape { .. }), "{}: {:?}", source, err);
+        }
+    }
+
+    #[test]
+    fn test_raw_strings_and_runes() {
+        let mut lexer = Lexer::new("`a\\n\r\nb` 'x' '\\'' '\\u00e9'");
+        let tokens = lexer.tokenize_all().unwrap();
+        assert_eq!(tokens, vec![
+            TokenType::Cadena("a\\n\nb".to_string()),
+            TokenType::Char('x'),
+            TokenType::Char('\''),
+            TokenType::Char('é'),
+        ]);
+        for source in ["''", "'ab'", "'a", "`open"] {
+            assert!(Lexer::new(source).tokenize_all().is_err(), "{}", source);
+        }
+    }
+
     #[test]
     fn test_peek_and_next_token() {
         let mut lexer = Lexer::new("42");
@@ -339,10 +946,127 @@ mod tests {
         assert_eq!(peeked, next);
     }
 
+    #[test]
+    fn test_bitwise_operators() {
+        let mut lexer = Lexer::new("a % b & c | d ^ e &^ f << g >> h <= i && j");
+        let operators: Vec<TokenType> = lexer.tokenize_all().unwrap()
+            .into_iter()
+            .filter(|t| matches!(t, TokenType::Operator(_)))
+            .collect();
+        let expected = ["%", "&", "|", "^", "&^", "<<", ">>", "<=", "&&"];
+        assert_eq!(operators, expected.iter().map(|op| TokenType::Operator(op.to_string())).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_assignment_operators() {
+        let mut lexer = Lexer::new("x++ y-- a += b <<= c &^= d >>= e %= f ^= g");
+        let operators: Vec<TokenType> = lexer.tokenize_all().unwrap()
+            .into_iter()
+            .filter(|t| matches!(t, TokenType::Operator(_)))
+            .collect();
+        let expected = ["++", "--", "+=", "<<=", "&^=", ">>=", "%=", "^="];
+        assert_eq!(operators, expected.iter().map(|op| TokenType::Operator(op.to_string())).collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_balanced_symbols() {
         let mut lexer = Lexer::new("({[]})");
         let tokens = lexer.tokenize_all().unwrap();
         assert_eq!(tokens.len(), 6);
     }
+
+    #[test]
+    fn test_colon_is_a_separator() {
+        let tokens = Lexer::new("a[1:]").tokenize_all().unwrap();
+        assert_eq!(tokens[3], TokenType::Punctuation { raw: ':', kind: PunctuationKind::Separator });
+    }
+
+    #[test]
+    fn test_dot_outside_numbers() {
+        let tokens = Lexer::new("p.X 1.5").tokenize_all().unwrap();
+        assert_eq!(tokens[1], TokenType::Punctuation { raw: '.', kind: PunctuationKind::Separator });
+        assert!(matches!(&tokens[3], TokenType::Numero { raw, .. } if raw == "1.5"));
+    }
+
+    #[test]
+    fn test_categories_and_lexemes() {
+        let tokens = Lexer::new("x = [0x1F, 2.5, \"a\\n\", 'b', true, func]").tokenize_all_with_trivia().unwrap();
+        let summary: Vec<(TokenCategory, &str)> = tokens.iter()
+            .filter(|t| !matches!(t.token, TokenType::Punctuation { raw: ',', .. }))
+            .map(|t| (t.token.category(), t.lexeme.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            (TokenCategory::Identifier, "x"),
+            (TokenCategory::Operator, "="),
+            (TokenCategory::Punctuation, "["),
+            (TokenCategory::Number, "0x1F"),
+            (TokenCategory::Number, "2.5"),
+            (TokenCategory::String, "\"a\\n\""),
+            (TokenCategory::Rune, "'b'"),
+            (TokenCategory::Boolean, "true"),
+            (TokenCategory::Keyword, "func"),
+            (TokenCategory::Punctuation, "]"),
+            (TokenCategory::EOF, ""),
+        ]);
+        assert_eq!(tokens[3].token.numeric_kind(), Some(NumericHint::Integer));
+        assert_eq!(tokens[2].token.bracket_depth(), Some(0));
+        assert_eq!(tokens[0].token.bracket_depth(), None);
+    }
+
+    #[test]
+    fn test_token_positions() {
+        let tokens = Lexer::new("x = 1\n  // note\n  yy += \"é\" + z").tokenize_all_with_trivia().unwrap();
+        let positions: Vec<(&str, usize, usize)> = tokens.iter()
+            .map(|t| (t.lexeme.as_str(), t.line, t.column))
+            .collect();
+        assert_eq!(positions, vec![
+            ("x", 1, 1), ("=", 1, 3), ("1", 1, 5),
+            ("yy", 3, 3), ("+=", 3, 6), ("\"é\"", 3, 9), ("+", 3, 13), ("z", 3, 15),
+            ("", 3, 16),
+        ]);
+        
+        let error = Lexer::new("a = \"open").tokenize_all().unwrap_err();
+        assert_eq!(error.kind(), "unterminated_string");
+        assert_eq!(error.position(), Some((1, 5)));
+    }
+
+    #[test]
+    fn test_comments_are_skipped() {
+        let mut lexer = Lexer::new("a // line\n/* block\n */ / b");
+        let tokens = lexer.tokenize_all().unwrap();
+        assert_eq!(tokens, vec![
+            TokenType::Identificador("a".to_string()),
+            TokenType::Operator("/".to_string()),
+            TokenType::Identificador("b".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_unterminated_block_comment() {
+        let mut lexer = Lexer::new("x\n  /* never closed");
+        let err = lexer.tokenize_all().unwrap_err();
+        assert!(matches!(err, LexerError::UnterminatedComment { line: 2, col: 3 }));
+    }
+
+    #[test]
+    fn test_nested_block_comment() {
+        let mut lexer = Lexer::new("/* outer /* inner */ */");
+        let err = lexer.tokenize_all().unwrap_err();
+        assert!(matches!(err, LexerError::NestedBlockComment { line: 1, col: 10 }));
+    }
+
+    #[test]
+    fn test_trivia_is_attached_to_next_token() {
+        let mut lexer = Lexer::new("// note\nx /* c */").keep_trivia(true);
+        let tokens = lexer.tokenize_all_with_trivia().unwrap();
+        assert_eq!(tokens[0].leading_trivia, vec![
+            Trivia::LineComment("// note".to_string()),
+            Trivia::Whitespace("\n".to_string()),
+        ]);
+        assert_eq!(tokens[1].token, TokenType::EOF);
+        assert_eq!(tokens[1].leading_trivia, vec![
+            Trivia::Whitespace(" ".to_string()),
+            Trivia::BlockComment("/* c */".to_string()),
+        ]);
+    }
 }
@@ -2,3 +2,5 @@ pub mod lexer;
 pub mod parser;
 pub mod ast;
 pub mod graphviz;
+pub mod printer;
+pub mod semantic;
@@ -1,6 +1,7 @@
 // Importamos tipos necesarios desde el módulo del lexer y del AST
-use crate::lexer::lexer::{Lexer, TokenType, LexerError, NumericHint, PunctuationKind};
-use crate::ast::ASTNode;
+use crate::lexer::lexer::{Keyword, Lexer, TokenType, LexerError, NumericHint, NumericValue, PunctuationKind};
+use crate::ast::{ASTNode, StructField};
+use crate::printer::print_ast;
 use thiserror::Error;
 
 // Definimos los distintos tipos de errores que pueden surgir durante el parsing
@@ -20,22 +21,80 @@ pub enum ParseError {
     
     #[error("Invalid syntax: {message}")]
     InvalidSyntax { message: String },
+    
+    #[error("'{keyword}' is a reserved keyword and cannot be used as an identifier")]
+    KeywordAsIdentifier { keyword: Keyword },
+    
+    #[error("cannot assign to {target}: only variables can be assigned")]
+    InvalidAssignmentTarget { target: String },
+    
+    #[error("expression nesting exceeds {limit} levels")]
+    NestingTooDeep { limit: usize },
 }
 
+impl ParseError {
+    /// Nombre estable y legible por máquinas de la variante; los errores léxicos usan el del lexer
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ParseError::LexerError(e) => e.kind(),
+            ParseError::UnexpectedToken { .. } => "unexpected_token",
+            ParseError::UnexpectedEOF => "unexpected_eof",
+            ParseError::InvalidSyntax { .. } => "invalid_syntax",
+            ParseError::KeywordAsIdentifier { .. } => "keyword_as_identifier",
+            ParseError::InvalidAssignmentTarget { .. } => "invalid_assignment_target",
+            ParseError::NestingTooDeep { .. } => "nesting_too_deep",
+        }
+    }
+    
+    /// Línea y columna que el propio error indica; solo los errores léxicos las llevan
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            ParseError::LexerError(e) => e.position(),
+            _ => None,
+        }
+    }
+}
+
+// Profundidad máxima de anidamiento; evita desbordar la pila con entradas como "((((...".
+// Cabe con holgura en los hilos de 2 MiB de tokio incluso en builds de depuración.
+pub const MAX_NESTING_DEPTH: usize = 48;
+
+// Las cadenas "1+1+1+...", "a.b.b..." o "f()()..." se construyen en un bucle y no cuentan como
+// anidamiento, así que su árbol es tan profundo como largo es el programa. Las etapas que lo
+// recorren (análisis, impresión, Graphviz, serialización) recurren a esa profundidad. Pila por
+// byte de código, con margen sobre lo medido con "+1+1+..." (unos 3 KiB en depuración, 300 B en release).
+const STACK_BYTES_PER_SOURCE_BYTE: usize = if cfg!(debug_assertions) { 4096 } else { 512 };
+
+/// Pila que necesita un hilo para pasar un programa de `source_bytes` bytes por todas las etapas
+pub fn stack_size_for(source_bytes: usize) -> usize {
+    (2 << 20) + source_bytes.saturating_mul(STACK_BYTES_PER_SOURCE_BYTE)
+}
+
+// Operadores de asignación compuesta; cada uno aplica el operador binario sin el `=`
+pub const COMPOUND_ASSIGNMENT_OPERATORS: &[&str] = &[
+    "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<=", ">>=", "&^=",
+];
+
 // Estructura principal del parser, contiene un lexer y el token actual
 pub struct Parser<'a> {
     lexer: Lexer<'a>,
     current_token: Option<TokenType>,
+    // Línea y columna (desde 1) donde empieza el token actual
+    current_position: (usize, usize),
+    depth: usize,
 }
 
 impl<'a> Parser<'a> {
-    /// Crea una nueva instancia del parser a partir de una cadena de entrada
+    /// Crea una nueva instancia del parser a partir de una cadena de entrada. Falla si el primer
+    /// token no es válido; el error léxico lleva su posición.
     pub fn new(input: &'a str) -> Result<Parser<'a>, ParseError> {
         let mut lexer = Lexer::new(input);
-        let current_token = lexer.next_token().ok();
+        let first = lexer.next_token_with_trivia()?;
         Ok(Parser {
+            current_position: (first.line, first.column),
+            current_token: Some(first.token),
             lexer,
-            current_token,
+            depth: 0,
         })
     }
     
@@ -47,7 +106,7 @@ impl<'a> Parser<'a> {
             match token {
                 TokenType::EOF => break,
                 _ => {
-                    let stmt = self.parse_expression_statement()?;
+                    let stmt = self.parse_statement()?;
                     statements.push(stmt);
                     
                     // Skip optional semicolon
@@ -63,16 +122,61 @@ impl<'a> Parser<'a> {
     
     /// Avanza al siguiente token
     fn advance(&mut self) -> Result<(), ParseError> {
-        self.current_token = match self.lexer.next_token() {
-            Ok(token) => Some(token),
-            Err(e) => return Err(ParseError::LexerError(e)),
+        let token = match self.lexer.next_token_with_trivia() {
+            Ok(token) => token,
+            Err(e) => {
+                // El lexer ya consumió el carácter problemático, así que su cursor apunta a él
+                self.current_position = e.position().unwrap_or((self.lexer.cur_line, self.lexer.cur_col));
+                return Err(ParseError::LexerError(e));
+            }
         };
+        self.current_position = (token.line, token.column);
+        self.current_token = Some(token.token);
         Ok(())
     }
     
-    /// Parsea una sentencia de expresión simple
-    fn parse_expression_statement(&mut self) -> Result<ASTNode, ParseError> {
+    /// Línea y columna (desde 1) del token donde está el parser; tras un error, el token que lo provocó
+    pub fn position(&self) -> (usize, usize) {
+        self.current_position
+    }
+    
+    /// Parsea una sentencia: declaración de tipo, `x++`, `x--`, asignación compuesta (`x += 1`),
+    /// asignación múltiple (`a, b = b, a`) o expresión simple
+    fn parse_statement(&mut self) -> Result<ASTNode, ParseError> {
+        if let Some(TokenType::Keyword(Keyword::Type)) = &self.current_token {
+            return self.parse_type_declaration();
+        }
+        
         let expr = self.parse_expression()?;
+        
+        if self.at_punctuation(',') && !matches!(expr, ASTNode::Assignment { .. }) {
+            return self.parse_multiple_assignment(expr);
+        }
+        
+        if let Some(TokenType::Operator(ref op)) = &self.current_token {
+            if op == "++" || op == "--" {
+                let operator = op.clone();
+                Self::check_assignable(&expr)?;
+                self.advance()?;
+                return Ok(ASTNode::IncDec {
+                    target: Box::new(expr),
+                    operator,
+                });
+            }
+            if COMPOUND_ASSIGNMENT_OPERATORS.contains(&op.as_str()) {
+                let operator = op.clone();
+                Self::check_assignable(&expr)?;
+                self.advance()?;
+                // El valor no puede ser otra asignación (`x += y = 3`)
+                let value = self.nested(Self::parse_or)?;
+                return Ok(ASTNode::CompoundAssignment {
+                    target: Box::new(expr),
+                    operator,
+                    value: Box::new(value),
+                });
+            }
+        }
+        
         Ok(ASTNode::ExpressionStatement {
             expression: Box::new(expr),
         })
@@ -80,7 +184,18 @@ impl<'a> Parser<'a> {
     
     /// Parsea una expresión completa (punto de entrada para precedencia)
     fn parse_expression(&mut self) -> Result<ASTNode, ParseError> {
-        self.parse_assignment()
+        self.nested(Self::parse_assignment)
+    }
+    
+    /// Ejecuta una regla recursiva controlando la profundidad de anidamiento
+    fn nested(&mut self, rule: fn(&mut Self) -> Result<ASTNode, ParseError>) -> Result<ASTNode, ParseError> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(ParseError::NestingTooDeep { limit: MAX_NESTING_DEPTH });
+        }
+        self.depth += 1;
+        let result = rule(self);
+        self.depth -= 1;
+        result
     }
     
     /// Parsea expresiones de asignación (con precedencia más baja)
@@ -90,8 +205,9 @@ impl<'a> Parser<'a> {
         // Verifica si es una asignación (`=`)
         if let Some(TokenType::Operator(ref op)) = &self.current_token {
             if op == "=" {
+                Self::check_assignable(&left)?;
                 self.advance()?;
-                let right = self.parse_assignment()?;
+                let right = self.parse_expression()?;
                 left = ASTNode::Assignment {
                     left: Box::new(left),
                     right: Box::new(right),
@@ -102,6 +218,125 @@ impl<'a> Parser<'a> {
         Ok(left)
     }
     
+    /// Parsea `type Nombre struct { X int; Y, Z float64 }`; los campos pueden separarse con `;`
+    fn parse_type_declaration(&mut self) -> Result<ASTNode, ParseError> {
+        self.advance()?; // consume 'type'
+        let name = self.parse_identifier("type name")?;
+        
+        if !matches!(self.current_token, Some(TokenType::Keyword(Keyword::Struct))) {
+            return Err(ParseError::UnexpectedToken {
+                expected: "'struct' after type name".to_string(),
+                found: self.current_token.clone().unwrap_or(TokenType::EOF),
+            });
+        }
+        self.advance()?;
+        self.expect_punctuation('{', "'{' after 'struct'")?;
+        
+        let mut fields = Vec::new();
+        while !self.at_punctuation('}') {
+            let mut names = vec![self.parse_identifier("field name")?];
+            while self.at_punctuation(',') {
+                self.advance()?;
+                names.push(self.parse_identifier("field name")?);
+            }
+            let field_type = self.parse_type()?;
+            fields.extend(names.into_iter().map(|name| StructField { name, field_type: field_type.clone() }));
+            
+            if self.at_punctuation(';') {
+                self.advance()?;
+            }
+        }
+        self.expect_punctuation('}', "closing brace")?;
+        
+        Ok(ASTNode::TypeDeclaration { name, fields })
+    }
+    
+    /// Consume un identificador y devuelve su nombre
+    fn parse_identifier(&mut self, expected: &str) -> Result<String, ParseError> {
+        match &self.current_token {
+            Some(TokenType::Identificador(name)) => {
+                let name = name.clone();
+                self.advance()?;
+                Ok(name)
+            },
+            Some(TokenType::Keyword(keyword)) => Err(ParseError::KeywordAsIdentifier {
+                keyword: *keyword,
+            }),
+            _ => Err(ParseError::UnexpectedToken {
+                expected: expected.to_string(),
+                found: self.current_token.clone().unwrap_or(TokenType::EOF),
+            }),
+        }
+    }
+    
+    /// Parsea el resto de una asignación múltiple a partir de su primer destino.
+    /// Los elementos de cada lista se parsean sin `=` para que la coma no quede dentro de otra asignación.
+    fn parse_multiple_assignment(&mut self, first: ASTNode) -> Result<ASTNode, ParseError> {
+        let mut targets = vec![first];
+        while self.at_punctuation(',') {
+            self.advance()?;
+            targets.push(self.nested(Self::parse_or)?);
+        }
+        
+        match &self.current_token {
+            Some(TokenType::Operator(op)) if op == "=" => self.advance()?,
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "'=' after assignment targets".to_string(),
+                    found: self.current_token.clone().unwrap_or(TokenType::EOF),
+                })
+            },
+        }
+        for target in &targets {
+            Self::check_assignable(target)?;
+        }
+        
+        let mut values = vec![self.nested(Self::parse_or)?];
+        while self.at_punctuation(',') {
+            self.advance()?;
+            values.push(self.nested(Self::parse_or)?);
+        }
+        
+        Ok(ASTNode::MultipleAssignment { targets, values })
+    }
+    
+    /// Indica si el token actual es el signo de puntuación `raw`
+    fn at_punctuation(&self, raw: char) -> bool {
+        matches!(&self.current_token, Some(TokenType::Punctuation { raw: c, .. }) if *c == raw)
+    }
+    
+    /// Consume el signo de puntuación `raw` o devuelve un error describiendo lo esperado
+    fn expect_punctuation(&mut self, raw: char, expected: &str) -> Result<(), ParseError> {
+        if self.at_punctuation(raw) {
+            self.advance()
+        } else {
+            Err(ParseError::UnexpectedToken {
+                expected: expected.to_string(),
+                found: self.current_token.clone().unwrap_or(TokenType::EOF),
+            })
+        }
+    }
+    
+    /// Verifica que el lado izquierdo de una asignación sea una variable, un elemento indexado
+    /// o un campo (posiblemente entre paréntesis)
+    fn check_assignable(target: &ASTNode) -> Result<(), ParseError> {
+        fn is_assignable(node: &ASTNode) -> bool {
+            match node {
+                ASTNode::Identifier { .. } | ASTNode::Index { .. } | ASTNode::FieldAccess { .. } => true,
+                ASTNode::Parenthesized { expression } => is_assignable(expression),
+                _ => false,
+            }
+        }
+        
+        if is_assignable(target) {
+            Ok(())
+        } else {
+            Err(ParseError::InvalidAssignmentTarget {
+                target: print_ast(target),
+            })
+        }
+    }
+    
     /// Parsea operaciones OR (`||`)
     fn parse_or(&mut self) -> Result<ASTNode, ParseError> {
         let mut left = self.parse_and()?;
@@ -190,12 +425,12 @@ impl<'a> Parser<'a> {
         Ok(left)
     }
     
-    /// Parsea operaciones aritméticas de suma y resta
+    /// Parsea suma, resta y los operadores de bits `|` y `^` (mismo nivel que en Go)
     fn parse_addition(&mut self) -> Result<ASTNode, ParseError> {
         let mut left = self.parse_multiplication()?;
         
         while let Some(TokenType::Operator(ref op)) = &self.current_token {
-            if op == "+" || op == "-" {
+            if op == "+" || op == "-" || op == "|" || op == "^" {
                 let operator = op.clone();
                 self.advance()?;
                 let right = self.parse_multiplication()?;
@@ -212,12 +447,12 @@ impl<'a> Parser<'a> {
         Ok(left)
     }
     
-    /// Parsea multiplicación y división
+    /// Parsea multiplicación, división, módulo, desplazamientos, `&` y `&^` (mismo nivel que en Go)
     fn parse_multiplication(&mut self) -> Result<ASTNode, ParseError> {
         let mut left = self.parse_unary()?;
         
         while let Some(TokenType::Operator(ref op)) = &self.current_token {
-            if op == "*" || op == "/" {
+            if matches!(op.as_str(), "*" | "/" | "%" | "<<" | ">>" | "&" | "&^") {
                 let operator = op.clone();
                 self.advance()?;
                 let right = self.parse_unary()?;
@@ -234,13 +469,13 @@ impl<'a> Parser<'a> {
         Ok(left)
     }
     
-    /// Parsea expresiones unarias (`-`, `!`)
+    /// Parsea expresiones unarias (`-`, `!`, `^`)
     fn parse_unary(&mut self) -> Result<ASTNode, ParseError> {
         if let Some(TokenType::Operator(ref op)) = &self.current_token {
-            if op == "-" || op == "!" {
+            if op == "-" || op == "!" || op == "^" {
                 let operator = op.clone();
                 self.advance()?;
-                let operand = self.parse_unary()?;
+                let operand = self.nested(Self::parse_unary)?;
                 return Ok(ASTNode::UnaryOp {
                     operator,
                     operand: Box::new(operand),
@@ -248,13 +483,140 @@ impl<'a> Parser<'a> {
             }
         }
         
-        self.parse_primary()
+        self.parse_postfix()
+    }
+    
+    /// Parsea sufijos de llamada (`f(x)`, `s.upper()`), acceso a campo (`p.X`), indexación (`a[i]`)
+    /// y rebanado (`a[lo:hi]`) sobre una expresión primaria
+    fn parse_postfix(&mut self) -> Result<ASTNode, ParseError> {
+        let mut node = self.parse_primary()?;
+        
+        loop {
+            if self.at_punctuation('(') {
+                self.advance()?; // consume '('
+                let arguments = self.parse_arguments()?;
+                node = ASTNode::FunctionCall { callee: Box::new(node), arguments };
+                continue;
+            }
+            if self.at_punctuation('.') {
+                self.advance()?;
+                let field = self.parse_identifier("field name after '.'")?;
+                node = ASTNode::FieldAccess { target: Box::new(node), field };
+                continue;
+            }
+            if !self.at_punctuation('[') {
+                break;
+            }
+            self.advance()?;
+            let low = if self.at_punctuation(':') { None } else { Some(Box::new(self.parse_expression()?)) };
+            
+            node = if self.at_punctuation(':') {
+                self.advance()?;
+                let high = if self.at_punctuation(']') { None } else { Some(Box::new(self.parse_expression()?)) };
+                self.expect_punctuation(']', "closing bracket")?;
+                ASTNode::Slice { target: Box::new(node), low, high }
+            } else {
+                self.expect_punctuation(']', "closing bracket")?;
+                ASTNode::Index {
+                    target: Box::new(node),
+                    index: low.expect("an index without ':' always has an expression"),
+                }
+            };
+        }
+        
+        Ok(node)
     }
     
-    /// Parsea expresiones primarias: números, cadenas, identificadores, llamadas, paréntesis
+    /// Parsea los argumentos de una llamada, separados por comas, hasta el `)` de cierre
+    fn parse_arguments(&mut self) -> Result<Vec<ASTNode>, ParseError> {
+        let mut arguments = Vec::new();
+        while !self.at_punctuation(')') {
+            arguments.push(self.parse_expression()?);
+            if self.at_punctuation(',') {
+                self.advance()?;
+            } else {
+                break;
+            }
+        }
+        self.expect_punctuation(')', "closing parenthesis")?;
+        Ok(arguments)
+    }
+    
+    /// Parsea un tipo: un nombre (`int`) precedido de cualquier número de prefijos `[]`, `[N]` o `map[K]`.
+    /// Devuelve el tipo en forma canónica, con las longitudes en decimal.
+    fn parse_type(&mut self) -> Result<String, ParseError> {
+        let mut prefix = String::new();
+        
+        loop {
+            if let Some(TokenType::Keyword(Keyword::Map)) = &self.current_token {
+                self.advance()?;
+                self.expect_punctuation('[', "'[' after 'map'")?;
+                // El tipo de la clave es recursivo; se limita igual que las expresiones
+                if self.depth >= MAX_NESTING_DEPTH {
+                    return Err(ParseError::NestingTooDeep { limit: MAX_NESTING_DEPTH });
+                }
+                self.depth += 1;
+                let key = self.parse_type();
+                self.depth -= 1;
+                let key = key?;
+                self.expect_punctuation(']', "closing bracket after map key type")?;
+                prefix.push_str(&format!("map[{}]", key));
+                continue;
+            }
+            if !self.at_punctuation('[') {
+                break;
+            }
+            self.advance()?;
+            match &self.current_token {
+                Some(TokenType::Numero { raw, kind: NumericHint::Integer, value }) => {
+                    let NumericValue::Integer(length) = value else {
+                        return Err(ParseError::InvalidSyntax {
+                            message: format!("array length {} is too large", raw),
+                        });
+                    };
+                    prefix.push_str(&format!("[{}]", length));
+                    self.advance()?;
+                },
+                _ => prefix.push_str("[]"),
+            }
+            self.expect_punctuation(']', "closing bracket in type")?;
+        }
+        
+        let name = self.parse_identifier("type name")?;
+        Ok(prefix + &name)
+    }
+    
+    /// Parsea un literal compuesto (`[]int{1, 2, 3}`, `Point{X: 1}`) a partir de su tipo;
+    /// admite una coma final
+    fn parse_composite_literal(&mut self, literal_type: String) -> Result<ASTNode, ParseError> {
+        self.expect_punctuation('{', "'{' after composite literal type")?;
+        
+        let mut elements = Vec::new();
+        while !self.at_punctuation('}') {
+            let element = self.parse_expression()?;
+            if self.at_punctuation(':') {
+                self.advance()?;
+                let value = self.parse_expression()?;
+                elements.push(ASTNode::KeyValue { key: Box::new(element), value: Box::new(value) });
+            } else {
+                elements.push(element);
+            }
+            if self.at_punctuation(',') {
+                self.advance()?;
+            } else {
+                break;
+            }
+        }
+        self.expect_punctuation('}', "closing brace")?;
+        
+        Ok(ASTNode::CompositeLiteral { literal_type, elements })
+    }
+    
+    /// Parsea expresiones primarias: números, cadenas, identificadores, paréntesis,
+    /// literales compuestos de arreglos, slices, maps y structs
     fn parse_primary(&mut self) -> Result<ASTNode, ParseError> {
         match &self.current_token {
-            Some(TokenType::Numero { raw, kind }) => {
+            Some(TokenType::Numero { raw, kind, .. }) => {
                 let value = raw.clone();
                 let is_float = matches!(kind, NumericHint::Float);
                 self.advance()?;
@@ -265,6 +627,11 @@ impl<'a> Parser<'a> {
                 self.advance()?;
                 Ok(ASTNode::String { value })
             },
+            Some(TokenType::Char(value)) => {
+                let value = *value;
+                self.advance()?;
+                Ok(ASTNode::Char { value })
+            },
             Some(TokenType::Boolean(value)) => {
                 let value = *value;
                 self.advance()?;
@@ -274,36 +641,9 @@ impl<'a> Parser<'a> {
                 let name = name.clone();
                 self.advance()?;
                 
-                // Check for function call
-                if let Some(TokenType::Punctuation { raw: '(', kind: PunctuationKind::Open(_) }) = &self.current_token {
-                    self.advance()?; // consume '('
-                    let mut arguments = Vec::new();
-                    
-                    // Parse arguments
-                    while let Some(ref token) = &self.current_token {
-                        if let TokenType::Punctuation { raw: ')', kind: PunctuationKind::Close(_) } = token {
-                            break;
-                        }
-                        
-                        arguments.push(self.parse_expression()?);
-                        
-                        // Handle comma separation
-                        if let Some(TokenType::Punctuation { raw: ',', kind: PunctuationKind::Separator }) = &self.current_token {
-                            self.advance()?;
-                        }
-                    }
-                    
-                    // Consume closing parenthesis
-                    if let Some(TokenType::Punctuation { raw: ')', kind: PunctuationKind::Close(_) }) = &self.current_token {
-                        self.advance()?;
-                    } else {
-                        return Err(ParseError::UnexpectedToken {
-                            expected: "closing parenthesis".to_string(),
-                            found: self.current_token.clone().unwrap_or(TokenType::EOF),
-                        });
-                    }
-                    
-                    Ok(ASTNode::FunctionCall { name, arguments })
+                if self.at_punctuation('{') {
+                    // Literal de un tipo con nombre: `Point{X: 1, Y: 2}`
+                    self.parse_composite_literal(name)
                 } else {
                     Ok(ASTNode::Identifier { name })
                 }
@@ -325,6 +665,13 @@ impl<'a> Parser<'a> {
                     })
                 }
             },
+            Some(TokenType::Punctuation { raw: '[', .. }) | Some(TokenType::Keyword(Keyword::Map)) => {
+                let literal_type = self.parse_type()?;
+                self.parse_composite_literal(literal_type)
+            },
+            Some(TokenType::Keyword(keyword)) => Err(ParseError::KeywordAsIdentifier {
+                keyword: *keyword,
+            }),
             Some(token) => Err(ParseError::UnexpectedToken {
                 expected: "expression".to_string(),
                 found: token.clone(),
@@ -332,4 +679,231 @@ impl<'a> Parser<'a> {
             None => Err(ParseError::UnexpectedEOF),
         }
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<ASTNode, ParseError> {
+        Parser::new(input)?.parse()
+    }
+
+    #[test]
+    fn test_assignment_to_variable() {
+        assert!(parse("x = 1; (y) = 2; a = b = 3; n++; m += 1").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_assignment_targets() {
+        let cases = [
+            ("5 = x", "5"),
+            ("f(a) = 3", "f(a)"),
+            ("a + b = 1", "a + b"),
+            ("(a = b) = c", "(a = b)"),
+            ("\"s\"++", "\"s\""),
+            ("g() += 2", "g()"),
+            ("a, 5 = 1, 2", "5"),
+        ];
+        for (input, expected) in cases {
+            match parse(input) {
+                Err(ParseError::InvalidAssignmentTarget { target }) => assert_eq!(target, expected, "{}", input),
+                other => panic!("Expected invalid target for {}, got {:?}", input, other),
+            }
+        }
+    }
+    
+    #[test]
+    fn test_compound_assignment_value_is_not_an_assignment() {
+        let Ok(ASTNode::Program { statements }) = parse("x += y * 2") else {
+            panic!("Expected program");
+        };
+        assert!(matches!(&statements[0], ASTNode::CompoundAssignment { value, .. } if matches!(**value, ASTNode::BinaryOp { .. })));
+        assert!(parse("x += y = 3").is_err());
+    }
+    
+    #[test]
+    fn test_multiple_assignment() {
+        let ident = |name: &str| ASTNode::Identifier { name: name.to_string() };
+        let Ok(ASTNode::Program { statements }) = parse("a, b = b, a") else {
+            panic!("Expected program");
+        };
+        assert_eq!(statements, vec![ASTNode::MultipleAssignment {
+            targets: vec![ident("a"), ident("b")],
+            values: vec![ident("b"), ident("a")],
+        }]);
+        
+        // El parser acepta listas de distinta longitud; el análisis semántico las rechaza
+        assert!(parse("x, y = 1").is_ok());
+        assert!(parse("f(a, b); x, y = 1, g(2, 3)").is_ok());
+        assert!(matches!(parse("a, b"), Err(ParseError::UnexpectedToken { .. })));
+    }
+    
+    #[test]
+    fn test_index_and_slice_expressions() {
+        let ident = |name: &str| Box::new(ASTNode::Identifier { name: name.to_string() });
+        let number = |value: &str| Box::new(ASTNode::Number { value: value.to_string(), is_float: false });
+        let Ok(ASTNode::Program { statements }) = parse("a[i][0]; s[1:]; s[:n]; s[:]") else {
+            panic!("Expected program");
+        };
+        let expressions: Vec<ASTNode> = statements.into_iter().map(|stmt| match stmt {
+            ASTNode::ExpressionStatement { expression } => *expression,
+            other => panic!("Expected expression statement, got {:?}", other),
+        }).collect();
+        assert_eq!(expressions, vec![
+            ASTNode::Index {
+                target: Box::new(ASTNode::Index { target: ident("a"), index: ident("i") }),
+                index: number("0"),
+            },
+            ASTNode::Slice { target: ident("s"), low: Some(number("1")), high: None },
+            ASTNode::Slice { target: ident("s"), low: None, high: Some(ident("n")) },
+            ASTNode::Slice { target: ident("s"), low: None, high: None },
+        ]);
+        
+        // Un elemento indexado es asignable; una rebanada no
+        assert!(parse("a[0] = 1; a[i]++; a[1] += 2").is_ok());
+        assert!(matches!(parse("s[1:2] = t"), Err(ParseError::InvalidAssignmentTarget { .. })));
+    }
+    
+    #[test]
+    fn test_composite_literals() {
+        let Ok(ASTNode::Program { statements }) = parse("[0x3][]int{[]int{1}, []int{},}") else {
+            panic!("Expected program");
+        };
+        let ASTNode::ExpressionStatement { expression } = &statements[0] else {
+            panic!("Expected expression statement");
+        };
+        let ASTNode::CompositeLiteral { literal_type, elements } = &**expression else {
+            panic!("Expected composite literal, got {:?}", expression);
+        };
+        assert_eq!(literal_type, "[3][]int");
+        assert_eq!(elements.len(), 2);
+        
+        assert!(parse("[]int").is_err());
+        assert!(parse("[n]int{}").is_err());
+        assert!(parse("[1.5]int{}").is_err());
+    }
+    
+    #[test]
+    fn test_struct_declarations_and_literals() {
+        let Ok(ASTNode::Program { statements }) = parse("type Point struct { X, Y int; Label string }; p = Point{X: 1, Y: 2}; p.X = p.Y") else {
+            panic!("Expected program");
+        };
+        let field = |name: &str, field_type: &str| StructField { name: name.to_string(), field_type: field_type.to_string() };
+        assert_eq!(statements[0], ASTNode::TypeDeclaration {
+            name: "Point".to_string(),
+            fields: vec![field("X", "int"), field("Y", "int"), field("Label", "string")],
+        });
+        let expression = |stmt: &ASTNode| match stmt {
+            ASTNode::ExpressionStatement { expression } => (**expression).clone(),
+            other => panic!("Expected expression statement, got {:?}", other),
+        };
+        let ASTNode::Assignment { right, .. } = expression(&statements[1]) else {
+            panic!("Expected assignment, got {:?}", statements[1]);
+        };
+        let ASTNode::CompositeLiteral { literal_type, elements } = *right else {
+            panic!("Expected composite literal, got {:?}", right);
+        };
+        assert_eq!(literal_type, "Point");
+        assert!(elements.iter().all(|element| matches!(element, ASTNode::KeyValue { .. })));
+        let ASTNode::Assignment { left, .. } = expression(&statements[2]) else {
+            panic!("Expected assignment, got {:?}", statements[2]);
+        };
+        assert!(matches!(*left, ASTNode::FieldAccess { field, .. } if field == "X"));
+        
+        assert!(parse("type T int").is_err());
+        assert!(parse("p.").is_err());
+        assert!(matches!(parse("type func struct {}"), Err(ParseError::KeywordAsIdentifier { .. })));
+    }
+    
+    #[test]
+    fn test_map_types_and_literals() {
+        let literal_type = |input: &str| match parse(input) {
+            Ok(ASTNode::Program { statements }) => match &statements[0] {
+                ASTNode::ExpressionStatement { expression } => match &**expression {
+                    ASTNode::CompositeLiteral { literal_type, .. } => literal_type.clone(),
+                    other => panic!("Expected composite literal, got {:?}", other),
+                },
+                other => panic!("Expected expression statement, got {:?}", other),
+            },
+            other => panic!("Expected program for {}, got {:?}", input, other),
+        };
+        assert_eq!(literal_type("map[string]int{\"a\": 1}"), "map[string]int");
+        assert_eq!(literal_type("map[[2]int]map[string][]bool{}"), "map[[2]int]map[string][]bool");
+        assert_eq!(literal_type("[]map[string]int{}"), "[]map[string]int");
+        
+        assert!(parse("v, ok = m[\"a\"]; delete(m, \"a\")").is_ok());
+        assert!(parse("map[string]{}").is_err());
+        assert!(parse("map{}").is_err());
+    }
+    
+    #[test]
+    fn test_calls_on_callee_expressions() {
+        let ident = |name: &str| Box::new(ASTNode::Identifier { name: name.to_string() });
+        let Ok(ASTNode::Program { statements }) = parse("s.upper(); fns[0](x); make(1)(2, 3)") else {
+            panic!("Expected program");
+        };
+        let calls: Vec<ASTNode> = statements.into_iter().map(|stmt| match stmt {
+            ASTNode::ExpressionStatement { expression } => *expression,
+            other => panic!("Expected expression statement, got {:?}", other),
+        }).collect();
+        assert_eq!(calls[0], ASTNode::FunctionCall {
+            callee: Box::new(ASTNode::FieldAccess { target: ident("s"), field: "upper".to_string() }),
+            arguments: vec![],
+        });
+        assert_eq!(calls[1], ASTNode::FunctionCall {
+            callee: Box::new(ASTNode::Index {
+                target: ident("fns"),
+                index: Box::new(ASTNode::Number { value: "0".to_string(), is_float: false }),
+            }),
+            arguments: vec![*ident("x")],
+        });
+        assert!(matches!(&calls[2], ASTNode::FunctionCall { callee, arguments }
+            if matches!(**callee, ASTNode::FunctionCall { .. }) && arguments.len() == 2));
+        
+        assert!(parse("f(a b)").is_err());
+        assert!(parse("f(a,").is_err());
+        assert!(matches!(parse("f() = 1"), Err(ParseError::InvalidAssignmentTarget { .. })));
+    }
+    
+    #[test]
+    fn test_error_position_points_at_offending_token() {
+        let mut parser = Parser::new("x = 1\ny = (2 +\n  ]").unwrap();
+        let error = parser.parse().unwrap_err();
+        assert_eq!(error.kind(), "unbalanced_symbols");
+        assert_eq!(parser.position(), (3, 3));
+        
+        let mut parser = Parser::new("x = 1\ny = (2 + )").unwrap();
+        assert!(parser.parse().is_err());
+        assert_eq!(parser.position(), (2, 10));
+    }
+
+    #[test]
+    fn test_lexer_error_on_first_token_is_reported() {
+        let cases = [
+            ("@", "unknown_symbol", (1, 1)),
+            ("// comentario\n  @x = 1", "unknown_symbol", (2, 3)),
+            ("\"\\q\"", "invalid_escape", (1, 2)),
+            (")", "unbalanced_symbols", (1, 1)),
+            ("  0x", "invalid_numeric", (1, 3)),
+        ];
+        for (input, kind, position) in cases {
+            match Parser::new(input) {
+                Err(error) => {
+                    assert_eq!(error.kind(), kind, "{}", input);
+                    assert_eq!(error.position(), Some(position), "{}", input);
+                },
+                Ok(_) => panic!("Expected a lexer error for {:?}", input),
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_chains_are_not_nesting() {
+        // Las cadenas se construyen en un bucle; solo el anidamiento real cuenta para el límite
+        for link in ["+1", "&&b", ".b", "[0]", "[:1]", "()"] {
+            let chain = format!("x = a{}", link.repeat(999));
+            assert!(parse(&chain).is_ok(), "{}", link);
+        }
+    }
+}
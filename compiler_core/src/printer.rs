@@ -0,0 +1,164 @@
+use crate::ast::{ASTNode, StructField};
+
+/// Prints an AST back to source code that the `Parser` accepts.
+///
+/// Grouping is taken from the tree itself: `Parenthesized` nodes are printed
+/// as parentheses and no others are added, so a tree produced by the parser
+/// prints back to an equivalent program.
+pub fn print_ast(ast: &ASTNode) -> String {
+    let mut out = String::new();
+    print_node(ast, &mut out);
+    out
+}
+
+fn print_node(node: &ASTNode, out: &mut String) {
+    match node {
+        ASTNode::Number { value, .. } => out.push_str(value),
+        ASTNode::String { value } => {
+            out.push('"');
+            for c in value.chars() {
+                push_escaped(c, '"', out);
+            }
+            out.push('"');
+        },
+        ASTNode::Char { value } => {
+            out.push('\'');
+            push_escaped(*value, '\'', out);
+            out.push('\'');
+        },
+        ASTNode::Boolean { value } => out.push_str(if *value { "true" } else { "false" }),
+        ASTNode::Identifier { name } => out.push_str(name),
+        ASTNode::BinaryOp { left, operator, right } => {
+            print_node(left, out);
+            out.push(' ');
+            out.push_str(operator);
+            out.push(' ');
+            print_node(right, out);
+        },
+        ASTNode::UnaryOp { operator, operand } => {
+            let operand_src = print_ast(operand);
+            out.push_str(operator);
+            // Keep `- -x` from being lexed as the `--` operator
+            if operator.chars().last().is_some_and(|c| operand_src.starts_with(c)) {
+                out.push(' ');
+            }
+            out.push_str(&operand_src);
+        },
+        ASTNode::Assignment { left, right } => {
+            print_node(left, out);
+            out.push_str(" = ");
+            print_node(right, out);
+        },
+        ASTNode::MultipleAssignment { targets, values } => {
+            print_list(targets, out);
+            out.push_str(" = ");
+            print_list(values, out);
+        },
+        ASTNode::IncDec { target, operator } => {
+            print_node(target, out);
+            out.push_str(operator);
+        },
+        ASTNode::CompoundAssignment { target, operator, value } => {
+            print_node(target, out);
+            out.push(' ');
+            out.push_str(operator);
+            out.push(' ');
+            print_node(value, out);
+        },
+        ASTNode::FunctionCall { callee, arguments } => {
+            print_node(callee, out);
+            out.push('(');
+            print_list(arguments, out);
+            out.push(')');
+        },
+        ASTNode::CompositeLiteral { literal_type, elements } => {
+            out.push_str(literal_type);
+            out.push('{');
+            print_list(elements, out);
+            out.push('}');
+        },
+        ASTNode::Index { target, index } => {
+            print_node(target, out);
+            out.push('[');
+            print_node(index, out);
+            out.push(']');
+        },
+        ASTNode::Slice { target, low, high } => {
+            print_node(target, out);
+            out.push('[');
+            if let Some(low) = low {
+                print_node(low, out);
+            }
+            out.push(':');
+            if let Some(high) = high {
+                print_node(high, out);
+            }
+            out.push(']');
+        },
+        ASTNode::KeyValue { key, value } => {
+            print_node(key, out);
+            out.push_str(": ");
+            print_node(value, out);
+        },
+        ASTNode::FieldAccess { target, field } => {
+            print_node(target, out);
+            out.push('.');
+            out.push_str(field);
+        },
+        ASTNode::TypeDeclaration { name, fields } => {
+            out.push_str("type ");
+            out.push_str(name);
+            out.push(' ');
+            out.push_str(&print_struct_type(fields));
+        },
+        ASTNode::Parenthesized { expression } => {
+            out.push('(');
+            print_node(expression, out);
+            out.push(')');
+        },
+        ASTNode::Program { statements } => {
+            for (i, stmt) in statements.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(";\n");
+                }
+                print_node(stmt, out);
+            }
+        },
+        ASTNode::ExpressionStatement { expression } => print_node(expression, out),
+    }
+}
+
+/// Prints a struct type the way Go spells it, e.g. `struct { X int; Y float64 }`
+pub fn print_struct_type(fields: &[StructField]) -> String {
+    if fields.is_empty() {
+        return "struct {}".to_string();
+    }
+    let fields: Vec<String> = fields.iter().map(|f| format!("{} {}", f.name, f.field_type)).collect();
+    format!("struct {{ {} }}", fields.join("; "))
+}
+
+/// Prints nodes separated by `, `
+fn print_list(nodes: &[ASTNode], out: &mut String) {
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        print_node(node, out);
+    }
+}
+
+/// Writes one character of a string or rune literal, escaping it if needed
+fn push_escaped(c: char, quote: char, out: &mut String) {
+    match c {
+        '\\' => out.push_str("\\\\"),
+        '\n' => out.push_str("\\n"),
+        '\r' => out.push_str("\\r"),
+        '\t' => out.push_str("\\t"),
+        c if c == quote => {
+            out.push('\\');
+            out.push(c);
+        },
+        c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+        c => out.push(c),
+    }
+}
@@ -0,0 +1,1416 @@
+use crate::ast::{ASTNode, StructField};
+use crate::lexer::NumericValue;
+use crate::printer::{print_ast, print_struct_type};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SemanticStep {
+    pub step_number: usize,
+    pub description: String,
+    pub node_type: String,
+    pub action: String,
+    pub symbol_added: Option<String>,
+    pub type_check: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SymbolInfo {
+    pub name: String,
+    pub symbol_type: String,
+    pub data_type: String,
+    pub scope: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TypeCheck {
+    pub expression: String,
+    pub expected_type: String,
+    pub actual_type: String,
+    pub is_valid: bool,
+    pub error_message: Option<String>,
+}
+
+/// Everything the semantic pass records while walking a program
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SemanticAnalysis {
+    pub steps: Vec<SemanticStep>,
+    pub symbol_table: Vec<SymbolInfo>,
+    pub type_checks: Vec<TypeCheck>,
+}
+
+/// Steps recorded so far, each with the type checks it made. A node's step comes before
+/// those of its subexpressions but needs their types, so it reserves its place first and
+/// fills it in once they are analyzed.
+#[derive(Default)]
+struct Trace {
+    entries: Vec<Option<(SemanticStep, Vec<TypeCheck>)>>,
+}
+
+impl Trace {
+    /// Keeps a place for a step that can only be written after the ones that follow it
+    fn reserve(&mut self) -> usize {
+        self.entries.push(None);
+        self.entries.len() - 1
+    }
+    
+    fn fill(&mut self, slot: usize, step: SemanticStep, type_checks: Vec<TypeCheck>) {
+        self.entries[slot] = Some((step, type_checks));
+    }
+    
+    fn push(&mut self, step: SemanticStep, type_checks: Vec<TypeCheck>) {
+        self.entries.push(Some((step, type_checks)));
+    }
+    
+    /// Numbers the steps in order, skipping places that were never filled
+    fn finish(self) -> (Vec<SemanticStep>, Vec<TypeCheck>) {
+        let mut steps = Vec::new();
+        let mut type_checks = Vec::new();
+        for (mut step, checks) in self.entries.into_iter().flatten() {
+            step.step_number = steps.len() + 1;
+            steps.push(step);
+            type_checks.extend(checks);
+        }
+        (steps, type_checks)
+    }
+}
+
+/// Runs semantic analysis over a parsed program
+pub fn analyze(ast: &ASTNode) -> SemanticAnalysis {
+    let mut trace = Trace::default();
+    let mut symbol_table = Vec::new();
+    
+    // Step 1: Initialize semantic analysis
+    trace.push(SemanticStep {
+        description: "Iniciando análisis semántico".to_string(),
+        node_type: "Program".to_string(),
+        action: "Crear tabla de símbolos global".to_string(),
+        ..Default::default()
+    }, Vec::new());
+    
+    // Step 2: Analyze AST nodes
+    analyze_node(ast, &mut trace, &mut symbol_table);
+    
+    // Final step
+    trace.push(SemanticStep {
+        description: "Análisis semántico completado".to_string(),
+        node_type: "Program".to_string(),
+        action: "Validación final".to_string(),
+        ..Default::default()
+    }, Vec::new());
+    
+    let (steps, type_checks) = trace.finish();
+    SemanticAnalysis {
+        steps,
+        symbol_table,
+        type_checks,
+    }
+}
+
+/// Integer types accepted by `%`, the bitwise operators and shifts
+fn is_integer_type(data_type: &str) -> bool {
+    data_type == "int" || data_type == "rune" || data_type == "byte"
+}
+
+/// Functions built into the language, called by bare name
+const BUILTIN_FUNCTIONS: &[&str] = &["len", "append", "delete"];
+
+/// Methods every string value has: name, parameter types and result type
+const STRING_METHODS: &[(&str, &[&str], &str)] = &[
+    ("upper", &[], "string"),
+    ("lower", &[], "string"),
+    ("len", &[], "int"),
+    ("contains", &["string"], "bool"),
+];
+
+/// Name of the built-in function a call's callee refers to, if any
+fn builtin_name(callee: &ASTNode) -> Option<&'static str> {
+    match callee {
+        ASTNode::Identifier { name } => BUILTIN_FUNCTIONS.iter().copied().find(|builtin| builtin == name),
+        _ => None,
+    }
+}
+
+/// Looks up a built-in method by receiver type, returning its parameter and result types
+fn resolve_method(receiver_type: &str, method: &str) -> Option<(&'static [&'static str], &'static str)> {
+    let methods = match receiver_type {
+        "string" => STRING_METHODS,
+        _ => return None,
+    };
+    methods.iter()
+        .find(|(name, ..)| *name == method)
+        .map(|(_, parameters, result)| (*parameters, *result))
+}
+
+/// Type given to expressions whose type cannot be inferred; checks involving it are skipped
+const UNKNOWN_TYPE: &str = "unknown";
+
+/// Types every program can use without declaring them
+const BUILTIN_TYPES: &[&str] = &["int", "float64", "string", "bool", "rune", "byte"];
+
+/// Whether `data_type` is built in, declared in the symbol table, or an array, slice or map of such types
+fn is_known_type(data_type: &str, symbol_table: &[SymbolInfo]) -> bool {
+    if let Some((key, value)) = map_types(data_type) {
+        return is_known_type(key, symbol_table) && is_known_type(value, symbol_table);
+    }
+    match element_type(data_type) {
+        Some(element) => is_known_type(element, symbol_table),
+        None => BUILTIN_TYPES.contains(&data_type)
+            || symbol_table.iter().any(|sym| sym.symbol_type == "Type" && sym.name == data_type),
+    }
+}
+
+/// Fields of a declared struct type, read back from its `struct { X int; Y float64 }` spelling
+fn struct_fields<'a>(type_name: &str, symbol_table: &'a [SymbolInfo]) -> Option<Vec<(&'a str, &'a str)>> {
+    let symbol = symbol_table.iter().find(|sym| sym.symbol_type == "Type" && sym.name == type_name)?;
+    let body = symbol.data_type.strip_prefix("struct {")?.strip_suffix('}')?;
+    Some(body.split(';').filter_map(|field| field.trim().split_once(' ')).collect())
+}
+
+/// Element type of an array (`[3]int`) or slice (`[]int`) type
+fn element_type(data_type: &str) -> Option<&str> {
+    let rest = data_type.strip_prefix('[')?;
+    rest.split_once(']').map(|(_, element)| element)
+}
+
+/// Key and value types of a map type such as `map[string][]int`
+fn map_types(data_type: &str) -> Option<(&str, &str)> {
+    let rest = data_type.strip_prefix("map[")?;
+    // The key may itself contain brackets, as in `map[[2]int]bool`
+    let mut depth = 0;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return Some((&rest[..i], &rest[i + 1..])),
+            ']' => depth -= 1,
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Whether values of `data_type` can be compared with `==` and so used as map keys
+fn is_comparable_type(data_type: &str) -> bool {
+    map_types(data_type).is_none()
+        && !data_type.starts_with("[]")
+        && element_type(data_type).is_none_or(is_comparable_type)
+}
+
+
+/// Length of a fixed-size array type such as `[3]int`
+fn array_length(data_type: &str) -> Option<u64> {
+    let rest = data_type.strip_prefix('[')?;
+    rest.split_once(']')?.0.parse().ok()
+}
+
+/// Types that `len`, indexing and slicing accept
+fn is_sequence_type(data_type: &str) -> bool {
+    data_type == "string" || element_type(data_type).is_some()
+}
+
+/// Whether a value of `value_type` can be stored where `target_type` is expected.
+/// Integer constants may go into float variables, as with untyped constants in Go.
+fn is_assignable_type(target_type: &str, value_type: &str) -> bool {
+    target_type == value_type
+        || target_type == UNKNOWN_TYPE
+        || value_type == UNKNOWN_TYPE
+        || (target_type == "float64" && value_type == "int")
+}
+
+/// Records a failed type check and returns its message for the step's error
+fn type_mismatch(type_checks: &mut Vec<TypeCheck>, expression: String, expected: &str, actual: &str,
+                 message: String) -> String {
+    type_checks.push(TypeCheck {
+        expression,
+        expected_type: expected.to_string(),
+        actual_type: actual.to_string(),
+        is_valid: false,
+        error_message: Some(message.clone()),
+    });
+    message
+}
+
+/// Whether `node` is an integer literal, possibly negated or parenthesized. Like an untyped
+/// constant in Go, it takes the integer type of the other operand, so `r & 0xFF` stays a rune.
+fn is_untyped_integer(node: &ASTNode) -> bool {
+    match node {
+        ASTNode::Number { is_float, .. } => !is_float,
+        ASTNode::UnaryOp { operator, operand } if operator == "-" || operator == "+" => is_untyped_integer(operand),
+        ASTNode::Parenthesized { expression } => is_untyped_integer(expression),
+        _ => false,
+    }
+}
+
+/// Operand types of an integer-only operator once an untyped integer constant on one side
+/// takes the other side's integer type
+fn integer_operand_types(left: &ASTNode, left_type: &str, right: &ASTNode, right_type: &str) -> (String, String) {
+    if is_untyped_integer(left) && is_integer_type(right_type) {
+        (right_type.to_string(), right_type.to_string())
+    } else if is_untyped_integer(right) && is_integer_type(left_type) {
+        (left_type.to_string(), left_type.to_string())
+    } else {
+        (left_type.to_string(), right_type.to_string())
+    }
+}
+
+/// Result type of an integer-only operator, which is the left operand's type
+fn integer_result(left_type: &str, right_type: &str) -> String {
+    if is_integer_type(left_type) && is_integer_type(right_type) {
+        left_type.to_string()
+    } else {
+        UNKNOWN_TYPE.to_string()
+    }
+}
+
+/// Infers the Go type of an expression from its literals and the symbol table
+pub fn infer_type_from_node(node: &ASTNode, symbol_table: &[SymbolInfo]) -> String {
+    analyze_node(node, &mut Trace::default(), &mut symbol_table.to_vec())
+}
+
+/// Records the steps and type checks for `node` and its subexpressions, returning its type.
+/// Each `analyze_*` function only reserves its step and recurses; the `check_*` function it
+/// hands the subexpression types to builds the step, which keeps long chains cheap on the stack.
+fn analyze_node(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    match node {
+        ASTNode::Identifier { name } => analyze_identifier(name, trace, symbol_table),
+        
+        ASTNode::Assignment { left, right } => {
+            analyze_assignment(left, right, trace, symbol_table);
+            UNKNOWN_TYPE.to_string()
+        },
+        
+        ASTNode::MultipleAssignment { targets, values } => {
+            analyze_multiple_assignment(node, targets, values, trace, symbol_table);
+            UNKNOWN_TYPE.to_string()
+        },
+        
+        ASTNode::IncDec { target, operator } => {
+            analyze_update(target, operator, None, trace, symbol_table);
+            UNKNOWN_TYPE.to_string()
+        },
+        
+        ASTNode::CompoundAssignment { target, operator, value } => {
+            analyze_update(target, operator, Some(value), trace, symbol_table);
+            UNKNOWN_TYPE.to_string()
+        },
+        
+        ASTNode::CompositeLiteral { .. } => analyze_composite_literal(node, trace, symbol_table),
+        
+        ASTNode::TypeDeclaration { name, fields } => {
+            analyze_type_declaration(name, fields, trace, symbol_table);
+            UNKNOWN_TYPE.to_string()
+        },
+        
+        ASTNode::FieldAccess { .. } => analyze_field_access(node, trace, symbol_table),
+        
+        ASTNode::Index { .. } => analyze_index(node, trace, symbol_table).0,
+        
+        ASTNode::Slice { .. } => analyze_slice(node, trace, symbol_table),
+        
+        ASTNode::FunctionCall { callee, .. } if matches!(&**callee, ASTNode::FieldAccess { .. }) => {
+            analyze_method_call(node, trace, symbol_table)
+        },
+        
+        ASTNode::FunctionCall { callee, .. } if builtin_name(callee).is_some() => {
+            analyze_builtin_call(node, trace, symbol_table)
+        },
+        
+        ASTNode::FunctionCall { .. } => analyze_function_call(node, trace, symbol_table),
+        
+        // Parentheses only group; what matters is the expression inside
+        ASTNode::Parenthesized { expression } => analyze_node(expression, trace, symbol_table),
+        
+        ASTNode::UnaryOp { .. } => analyze_unary_op(node, trace, symbol_table),
+        
+        ASTNode::BinaryOp { .. } => analyze_binary_op(node, trace, symbol_table),
+        
+        ASTNode::Number { value, is_float } => analyze_number(value, *is_float, false, trace),
+        
+        ASTNode::String { value } => {
+            trace.push(SemanticStep {
+                description: format!("Literal de cadena: \"{}\" (tipo: string)", value),
+                node_type: "String".to_string(),
+                action: "Verificar tipo string".to_string(),
+                symbol_added: None,
+                type_check: Some("string".to_string()),
+                error: None,
+                ..Default::default()
+            }, Vec::new());
+            "string".to_string()
+        },
+        
+        ASTNode::Char { value } => {
+            trace.push(SemanticStep {
+                description: format!("Literal rune: '{}' (tipo: rune)", value.escape_default()),
+                node_type: "Char".to_string(),
+                action: "Verificar tipo rune".to_string(),
+                symbol_added: None,
+                type_check: Some("rune".to_string()),
+                error: None,
+                ..Default::default()
+            }, Vec::new());
+            "rune".to_string()
+        },
+        
+        ASTNode::Boolean { value } => {
+            trace.push(SemanticStep {
+                description: format!("Literal booleano: {} (tipo: bool)", value),
+                node_type: "Boolean".to_string(),
+                action: "Verificar tipo booleano".to_string(),
+                symbol_added: None,
+                type_check: Some("bool".to_string()),
+                error: None,
+                ..Default::default()
+            }, Vec::new());
+            "bool".to_string()
+        },
+        
+        ASTNode::Program { statements } => {
+            for stmt in statements {
+                analyze_node(stmt, trace, symbol_table);
+            }
+            UNKNOWN_TYPE.to_string()
+        },
+        
+        ASTNode::ExpressionStatement { expression } => {
+            analyze_node(expression, trace, symbol_table);
+            UNKNOWN_TYPE.to_string()
+        },
+        
+        _ => {
+            trace.push(SemanticStep {
+                description: "Analizando nodo".to_string(),
+                node_type: node.node_type().to_string(),
+                action: "Procesar nodo".to_string(),
+                symbol_added: None,
+                type_check: None,
+                error: None,
+                ..Default::default()
+            }, Vec::new());
+            UNKNOWN_TYPE.to_string()
+        }
+    }
+}
+
+/// Checks that a variable is declared, returning its type
+fn analyze_identifier(name: &str, trace: &mut Trace, symbol_table: &[SymbolInfo]) -> String {
+    // Check if identifier is declared
+    let Some(symbol) = symbol_table.iter().find(|sym| sym.name == *name) else {
+        trace.push(SemanticStep {
+            description: format!("Variable '{}' no declarada", name),
+            node_type: "Identifier".to_string(),
+            action: "Verificar declaración".to_string(),
+            symbol_added: None,
+            type_check: None,
+            error: Some(format!("Variable '{}' no está declarada", name)),
+            ..Default::default()
+        }, Vec::new());
+        return UNKNOWN_TYPE.to_string();
+    };
+    
+    trace.push(SemanticStep {
+        description: format!("Variable '{}' encontrada en tabla de símbolos", name),
+        node_type: "Identifier".to_string(),
+        action: "Verificar declaración".to_string(),
+        symbol_added: None,
+        type_check: Some(symbol.data_type.clone()),
+        error: None,
+        ..Default::default()
+    }, Vec::new());
+    symbol.data_type.clone()
+}
+
+/// Checks an assignment, records its target and analyzes the value
+fn analyze_assignment(left: &ASTNode, right: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) {
+    trace.push(SemanticStep {
+        description: "Analizando asignación".to_string(),
+        node_type: "Assignment".to_string(),
+        action: "Verificar tipos de asignación".to_string(),
+        symbol_added: None,
+        type_check: Some("Assignment check".to_string()),
+        error: None,
+        ..Default::default()
+    }, Vec::new());
+    
+    // Analyze left side (should be identifier)
+    match left {
+        ASTNode::Identifier { name } => {
+            // The target's step comes first, but its type comes from the right side
+            let slot = trace.reserve();
+            let right_type = analyze_node(right, trace, symbol_table);
+            record_assignment(name, right_type, slot, trace, symbol_table);
+        },
+        ASTNode::Index { .. } | ASTNode::FieldAccess { .. } => {
+            let slot = trace.reserve();
+            let (element, container_type) = analyze_store_target(left, trace, symbol_table);
+            let right_type = analyze_node(right, trace, symbol_table);
+            analyze_element_assignment(left, &element, &container_type, &right_type, slot, trace);
+        },
+        _ => {
+            analyze_node(right, trace, symbol_table);
+        },
+    }
+}
+
+/// Checks `a, b = x, y` and `v, ok = m[k]`, then records the targets
+fn analyze_multiple_assignment(node: &ASTNode, targets: &[ASTNode], values: &[ASTNode], trace: &mut Trace,
+                               symbol_table: &mut Vec<SymbolInfo>) {
+    let slot = trace.reserve();
+    // Every value is typed against the table as it was before the statement, so `a, b = b, a` swaps
+    let mut value_types = Vec::new();
+    let mut map_reads = Vec::new();
+    for value in values {
+        let (value_type, map_read) = analyze_value(value, trace, symbol_table);
+        value_types.push(value_type);
+        map_reads.push(map_read);
+    }
+    
+    // `v, ok = m[k]` assigns the element and whether the key was present
+    let comma_ok = targets.len() == 2 && values.len() == 1 && map_reads[0];
+    let count_error = (!comma_ok && targets.len() != values.len()).then(|| {
+        format!("Asignación con {} variables pero {} valores", targets.len(), values.len())
+    });
+    let description = if comma_ok {
+        format!("Analizando asignación coma-ok: {} (valor y presencia de la clave)", print_ast(&values[0]))
+    } else {
+        format!("Analizando asignación múltiple ({} variables, {} valores)", targets.len(), values.len())
+    };
+    let mut type_checks = Vec::new();
+    if count_error.is_some() {
+        type_checks.push(TypeCheck {
+            expression: print_ast(node),
+            expected_type: format!("{} valores", targets.len()),
+            actual_type: format!("{} valores", values.len()),
+            is_valid: false,
+            error_message: count_error.clone(),
+        });
+    }
+    trace.fill(slot, SemanticStep {
+        description,
+        node_type: "MultipleAssignment".to_string(),
+        action: "Verificar que el número de variables y valores coincida".to_string(),
+        symbol_added: None,
+        type_check: Some("Assignment check".to_string()),
+        error: count_error,
+        ..Default::default()
+    }, type_checks);
+    
+    if comma_ok {
+        value_types.push("bool".to_string());
+    }
+    for (target, value_type) in targets.iter().zip(value_types) {
+        match target {
+            ASTNode::Identifier { name } => {
+                let slot = trace.reserve();
+                record_assignment(name, value_type, slot, trace, symbol_table);
+            },
+            ASTNode::Index { .. } | ASTNode::FieldAccess { .. } => {
+                let slot = trace.reserve();
+                let (element, container_type) = analyze_store_target(target, trace, symbol_table);
+                analyze_element_assignment(target, &element, &container_type, &value_type, slot, trace);
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Analyzes a value of a multiple assignment, returning its type and whether it reads a map
+/// element, which also yields a presence flag in `v, ok = m[k]`
+fn analyze_value(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> (String, bool) {
+    match node {
+        ASTNode::Index { .. } => {
+            let (value_type, container_type) = analyze_index(node, trace, symbol_table);
+            let map_read = map_types(&container_type).is_some();
+            (value_type, map_read)
+        },
+        ASTNode::Parenthesized { expression } => analyze_value(expression, trace, symbol_table),
+        _ => (analyze_node(node, trace, symbol_table), false),
+    }
+}
+
+/// Checks a composite literal's elements against its type, returning the literal's type
+fn analyze_composite_literal(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    let ASTNode::CompositeLiteral { literal_type, elements } = node else { return UNKNOWN_TYPE.to_string() };
+    let slot = trace.reserve();
+    let is_struct = struct_fields(literal_type, symbol_table).is_some();
+    // The key and value types of each element; plain elements and struct fields have no key type
+    let mut element_types = Vec::new();
+    for item in elements {
+        let types = match item {
+            // Struct keys name fields, not variables
+            ASTNode::KeyValue { value, .. } if is_struct => {
+                (UNKNOWN_TYPE.to_string(), analyze_node(value, trace, symbol_table))
+            },
+            ASTNode::KeyValue { key, value } => {
+                (analyze_node(key, trace, symbol_table), analyze_node(value, trace, symbol_table))
+            },
+            _ => (UNKNOWN_TYPE.to_string(), analyze_node(item, trace, symbol_table)),
+        };
+        element_types.push(types);
+    }
+    
+    let mut type_checks = Vec::new();
+    let mut errors = Vec::new();
+    let fields = struct_fields(literal_type, symbol_table);
+    match (fields, element_type(literal_type)) {
+        (Some(fields), _) => {
+            errors.extend(check_struct_literal(literal_type, &fields, elements, &element_types, &mut type_checks));
+        },
+        (None, None) => match map_types(literal_type) {
+            Some((key, value)) => {
+                errors.extend(check_map_literal(literal_type, key, value, elements, &element_types, &mut type_checks));
+            },
+            None => errors.push(format!("Tipo de literal compuesto desconocido: {}", literal_type)),
+        },
+        (None, Some(element)) => {
+            if let Some(length) = array_length(literal_type).filter(|&length| elements.len() as u64 > length) {
+                errors.push(type_mismatch(&mut type_checks, print_ast(node), literal_type,
+                    &format!("{} elementos", elements.len()),
+                    format!("El literal tiene {} elementos pero {} solo admite {}", elements.len(), literal_type, length)));
+            }
+            for (item, (_, item_type)) in elements.iter().zip(&element_types) {
+                if let ASTNode::KeyValue { .. } = item {
+                    errors.push(format!("Los literales de {} no admiten claves: {}", literal_type, print_ast(item)));
+                    continue;
+                }
+                if !is_assignable_type(element, item_type) {
+                    errors.push(type_mismatch(&mut type_checks, print_ast(item), element, item_type,
+                        format!("No se puede usar {} como elemento de tipo {} en {}", item_type, element, literal_type)));
+                }
+            }
+        },
+    }
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Literal compuesto {} con {} elementos", literal_type, elements.len()),
+        node_type: "CompositeLiteral".to_string(),
+        action: "Verificar tipos de los elementos".to_string(),
+        symbol_added: None,
+        type_check: Some(literal_type.to_string()),
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+        ..Default::default()
+    }, type_checks);
+    literal_type.clone()
+}
+
+/// Declares a struct type, checking its fields
+fn analyze_type_declaration(name: &str, fields: &[StructField], trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) {
+    let struct_type = print_struct_type(fields);
+    let already_declared = symbol_table.iter().any(|sym| sym.name == *name);
+    let mut errors = Vec::new();
+    if already_declared {
+        errors.push(format!("'{}' ya está declarado", name));
+    }
+    for (i, field) in fields.iter().enumerate() {
+        if fields[..i].iter().any(|other| other.name == field.name) {
+            errors.push(format!("Campo '{}' duplicado en {}", field.name, name));
+        }
+        if field.field_type == *name {
+            errors.push(format!("Tipo recursivo inválido: el campo '{}' contiene {}", field.name, name));
+        } else if !is_known_type(&field.field_type, symbol_table)
+            && element_type(&field.field_type).is_none_or(|element| element != name)
+        {
+            errors.push(format!("Tipo desconocido '{}' en el campo '{}'", field.field_type, field.name));
+        }
+    }
+    
+    if !already_declared {
+        symbol_table.push(SymbolInfo {
+            name: name.to_string(),
+            symbol_type: "Type".to_string(),
+            data_type: struct_type.clone(),
+            scope: "Global".to_string(),
+            line: 1,
+        });
+    }
+    trace.push(SemanticStep {
+        description: format!("Tipo '{}' declarado: {}", name, struct_type),
+        node_type: "TypeDeclaration".to_string(),
+        action: "Agregar tipo a tabla de símbolos".to_string(),
+        symbol_added: (!already_declared).then(|| name.to_string()),
+        type_check: Some(struct_type),
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+        ..Default::default()
+    }, Vec::new());
+}
+
+/// Analyzes a field access and its target, returning the field's type
+fn analyze_field_access(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    let ASTNode::FieldAccess { target, .. } = node else { return UNKNOWN_TYPE.to_string() };
+    let slot = trace.reserve();
+    let target_type = analyze_node(target, trace, symbol_table);
+    check_field_access(node, &target_type, slot, trace, symbol_table)
+}
+
+/// Resolves a field access on a value of `target_type`, returning the field's type
+fn check_field_access(node: &ASTNode, target_type: &str, slot: usize, trace: &mut Trace, symbol_table: &[SymbolInfo]) -> String {
+    let ASTNode::FieldAccess { target, field } = node else { return UNKNOWN_TYPE.to_string() };
+    let fields = struct_fields(target_type, symbol_table);
+    let result_type = fields.as_ref()
+        .and_then(|fields| fields.iter().find(|(name, _)| name == field))
+        .map(|(_, field_type)| field_type.to_string())
+        .unwrap_or_else(|| UNKNOWN_TYPE.to_string());
+    let mut type_checks = Vec::new();
+    let error = match fields {
+        _ if target_type == UNKNOWN_TYPE => None,
+        None => Some(type_mismatch(&mut type_checks, print_ast(target), "struct", target_type,
+            format!("El tipo {} no tiene campos", target_type))),
+        Some(fields) if !fields.iter().any(|(name, _)| name == field) => {
+            Some(type_mismatch(&mut type_checks, print_ast(node), target_type, UNKNOWN_TYPE,
+                format!("El campo '{}' no existe en {}", field, target_type)))
+        },
+        Some(_) => None,
+    };
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Acceso a campo: {} (tipo: {})", print_ast(node), result_type),
+        node_type: "FieldAccess".to_string(),
+        action: "Resolver campo del struct".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", result_type)),
+        error,
+        ..Default::default()
+    }, type_checks);
+    result_type
+}
+
+/// Analyzes an index expression, its target and its index, returning the element's type and
+/// the indexed value's type
+fn analyze_index(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> (String, String) {
+    let ASTNode::Index { target, index } = node else { return (UNKNOWN_TYPE.to_string(), UNKNOWN_TYPE.to_string()) };
+    let slot = trace.reserve();
+    let target_type = analyze_node(target, trace, symbol_table);
+    let index_type = analyze_node(index, trace, symbol_table);
+    let result_type = check_index_expression(node, &target_type, &index_type, slot, trace);
+    (result_type, target_type)
+}
+
+/// Checks an index into a value of `target_type`, returning the element's type
+fn check_index_expression(node: &ASTNode, target_type: &str, index_type: &str, slot: usize, trace: &mut Trace) -> String {
+    let ASTNode::Index { target, index } = node else { return UNKNOWN_TYPE.to_string() };
+    let result_type = match (map_types(target_type), element_type(target_type)) {
+        (Some((_, value)), _) => value.to_string(),
+        (None, Some(element)) => element.to_string(),
+        (None, None) if target_type == "string" => "byte".to_string(),
+        (None, None) => UNKNOWN_TYPE.to_string(),
+    };
+    let mut type_checks = Vec::new();
+    let mut errors = Vec::new();
+    if let Some((key, _)) = map_types(target_type) {
+        if !is_assignable_type(key, index_type) {
+            errors.push(type_mismatch(&mut type_checks, print_ast(index), key, index_type,
+                format!("La clave de {} debe ser {}, no {}", target_type, key, index_type)));
+        }
+    } else {
+        if target_type != UNKNOWN_TYPE && !is_sequence_type(target_type) {
+            errors.push(type_mismatch(&mut type_checks, print_ast(target), "arreglo, slice, map o string", target_type,
+                format!("No se puede indexar un valor de tipo {}", target_type)));
+        }
+        errors.extend(check_index(index, index_type, target_type, false, &mut type_checks));
+    }
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Acceso a elemento: {} (tipo: {})", print_ast(node), result_type),
+        node_type: "Index".to_string(),
+        action: "Verificar valor indexable e índice entero".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", result_type)),
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+        ..Default::default()
+    }, type_checks);
+    result_type
+}
+
+/// Analyzes a slice expression, its target and its bounds, returning the slice's type
+fn analyze_slice(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    let ASTNode::Slice { target, low, high } = node else { return UNKNOWN_TYPE.to_string() };
+    let slot = trace.reserve();
+    let target_type = analyze_node(target, trace, symbol_table);
+    let bound_types: Vec<String> = low.iter().chain(high)
+        .map(|bound| analyze_node(bound, trace, symbol_table))
+        .collect();
+    check_slice(node, &target_type, &bound_types, slot, trace)
+}
+
+/// Checks a slice of a value of `target_type`, given the types of its bounds, returning the slice's type
+fn check_slice(node: &ASTNode, target_type: &str, bound_types: &[String], slot: usize, trace: &mut Trace) -> String {
+    let ASTNode::Slice { target, low, high } = node else { return UNKNOWN_TYPE.to_string() };
+    let result_type = match element_type(target_type) {
+        Some(element) => format!("[]{}", element),
+        None if target_type == "string" => "string".to_string(),
+        None => UNKNOWN_TYPE.to_string(),
+    };
+    let mut type_checks = Vec::new();
+    let mut errors = Vec::new();
+    if target_type != UNKNOWN_TYPE && !is_sequence_type(target_type) {
+        errors.push(type_mismatch(&mut type_checks, print_ast(target), "arreglo, slice o string", target_type,
+            format!("No se puede rebanar un valor de tipo {}", target_type)));
+    }
+    for (bound, bound_type) in low.iter().chain(high).zip(bound_types) {
+        errors.extend(check_index(bound, bound_type, target_type, true, &mut type_checks));
+    }
+    if let (Some(lo), Some(hi)) = (low.as_deref().and_then(constant_index), high.as_deref().and_then(constant_index)) {
+        if lo > hi {
+            errors.push(type_mismatch(&mut type_checks, print_ast(node), "lo <= hi", &format!("{} > {}", lo, hi),
+                format!("Índices de slice invertidos: {} > {}", lo, hi)));
+        }
+    }
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Rebanado: {} (tipo: {})", print_ast(node), result_type),
+        node_type: "Slice".to_string(),
+        action: "Verificar valor rebanable y límites enteros".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", result_type)),
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+        ..Default::default()
+    }, type_checks);
+    result_type
+}
+
+/// Analyzes a call to `len`, `append` or `delete` and its arguments, returning the call's type
+fn analyze_builtin_call(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    let ASTNode::FunctionCall { arguments, .. } = node else { return UNKNOWN_TYPE.to_string() };
+    let slot = trace.reserve();
+    let argument_types: Vec<String> = arguments.iter()
+        .map(|argument| analyze_node(argument, trace, symbol_table))
+        .collect();
+    check_builtin_call(node, &argument_types, slot, trace)
+}
+
+/// Checks the argument types of `len`, `append` or `delete`, returning the call's type
+fn check_builtin_call(node: &ASTNode, argument_types: &[String], slot: usize, trace: &mut Trace) -> String {
+    let ASTNode::FunctionCall { callee, arguments } = node else { return UNKNOWN_TYPE.to_string() };
+    let name = builtin_name(callee).expect("guarded by the match arm");
+    let mut type_checks = Vec::new();
+    let mut errors = Vec::new();
+    if name == "len" {
+        if arguments.len() != 1 {
+            errors.push(format!("len espera 1 argumento, recibió {}", arguments.len()));
+        } else if argument_types[0] != UNKNOWN_TYPE && !is_sequence_type(&argument_types[0])
+            && map_types(&argument_types[0]).is_none()
+        {
+            errors.push(type_mismatch(&mut type_checks, print_ast(&arguments[0]), "arreglo, slice, map o string",
+                &argument_types[0], format!("Argumento inválido para len: tipo {}", argument_types[0])));
+        }
+    } else if name == "delete" {
+        if arguments.len() != 2 {
+            errors.push(format!("delete espera 2 argumentos, recibió {}", arguments.len()));
+        } else if argument_types[0] != UNKNOWN_TYPE {
+            match map_types(&argument_types[0]) {
+                None => errors.push(type_mismatch(&mut type_checks, print_ast(&arguments[0]), "map", &argument_types[0],
+                    format!("El primer argumento de delete debe ser un map, no {}", argument_types[0]))),
+                Some((key, _)) if !is_assignable_type(key, &argument_types[1]) => {
+                    errors.push(type_mismatch(&mut type_checks, print_ast(&arguments[1]), key, &argument_types[1],
+                        format!("La clave de {} debe ser {}, no {}", argument_types[0], key, argument_types[1])));
+                },
+                Some(_) => {},
+            }
+        }
+    } else {
+        match argument_types.first().map(String::as_str) {
+            None => errors.push("append espera al menos 1 argumento".to_string()),
+            Some(UNKNOWN_TYPE) => {},
+            Some(slice_type) => match slice_type.strip_prefix("[]") {
+                None => errors.push(type_mismatch(&mut type_checks, print_ast(&arguments[0]), "slice", slice_type,
+                    format!("El primer argumento de append debe ser un slice, no {}", slice_type))),
+                Some(element) => {
+                    for (argument, argument_type) in arguments.iter().zip(argument_types).skip(1) {
+                        if !is_assignable_type(element, argument_type) {
+                            errors.push(type_mismatch(&mut type_checks, print_ast(argument), element, argument_type,
+                                format!("No se puede agregar {} a {}", argument_type, slice_type)));
+                        }
+                    }
+                },
+            },
+        }
+    }
+    let result_type = match name {
+        "len" => "int".to_string(),
+        "append" => argument_types.first().cloned().unwrap_or_else(|| UNKNOWN_TYPE.to_string()),
+        _ => UNKNOWN_TYPE.to_string(),
+    };
+    // `delete` is a statement-like call with no result
+    let shown_type = match name {
+        "delete" => "ninguno",
+        _ => &result_type,
+    };
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Llamada a función integrada {} (resultado: {})", name, shown_type),
+        node_type: "FunctionCall".to_string(),
+        action: "Verificar argumentos de función integrada".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", shown_type)),
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+        ..Default::default()
+    }, type_checks);
+    result_type
+}
+
+/// Analyzes a call to anything other than a built-in or a method, its callee and its arguments
+fn analyze_function_call(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    let ASTNode::FunctionCall { callee, arguments } = node else { return UNKNOWN_TYPE.to_string() };
+    let slot = trace.reserve();
+    let callee_type = analyze_node(callee, trace, symbol_table);
+    for argument in arguments {
+        analyze_node(argument, trace, symbol_table);
+    }
+    check_function_call(node, &callee_type, slot, trace)
+}
+
+/// Records a call whose callee is of `callee_type`. The language has no function values, so a
+/// callee of known type is an error.
+fn check_function_call(node: &ASTNode, callee_type: &str, slot: usize, trace: &mut Trace) -> String {
+    let ASTNode::FunctionCall { callee, .. } = node else { return UNKNOWN_TYPE.to_string() };
+    let mut type_checks = Vec::new();
+    let error = (callee_type != UNKNOWN_TYPE).then(|| {
+        type_mismatch(&mut type_checks, print_ast(callee), "función", callee_type,
+            format!("No se puede llamar a {}: es de tipo {}, no una función", print_ast(callee), callee_type))
+    });
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Llamada a función: {} (resultado: {})", print_ast(node), UNKNOWN_TYPE),
+        node_type: "FunctionCall".to_string(),
+        action: "Verificar función y argumentos".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", UNKNOWN_TYPE)),
+        error,
+        ..Default::default()
+    }, type_checks);
+    UNKNOWN_TYPE.to_string()
+}
+
+/// Analyzes a unary operation and its operand, returning the result's type
+fn analyze_unary_op(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    let ASTNode::UnaryOp { operator, operand } = node else { return UNKNOWN_TYPE.to_string() };
+    let slot = trace.reserve();
+    let operand_type = match &**operand {
+        // `-9223372036854775808` is a valid int, so the literal is checked negated
+        ASTNode::Number { value, is_float } if operator == "-" => analyze_number(value, *is_float, true, trace),
+        _ => analyze_node(operand, trace, symbol_table),
+    };
+    check_unary_op(node, &operand_type, slot, trace)
+}
+
+/// Checks a unary operation's operand type, returning the result's type
+fn check_unary_op(node: &ASTNode, operand_type: &str, slot: usize, trace: &mut Trace) -> String {
+    let ASTNode::UnaryOp { operator, operand } = node else { return UNKNOWN_TYPE.to_string() };
+    let result_type = match operator.as_str() {
+        "!" if operand_type == "bool" => "bool".to_string(),
+        "-" | "+" if operand_type == "int" || operand_type == "float64" => operand_type.to_string(),
+        "^" => integer_result(operand_type, operand_type),
+        _ => UNKNOWN_TYPE.to_string(),
+    };
+    
+    let is_valid = match operator.as_str() {
+        "!" => operand_type == "bool",
+        "-" | "+" => operand_type == "int" || operand_type == "float64",
+        "^" => is_integer_type(operand_type),
+        _ => true,
+    };
+    
+    // Add type check
+    let type_check = TypeCheck {
+        expression: format!("{}{}",
+            operator,
+            match &**operand {
+                ASTNode::Identifier { name } => name.clone(),
+                ASTNode::Number { value, .. } => value.clone(),
+                ASTNode::Boolean { value } => value.to_string(),
+                _ => "expr".to_string(),
+            }
+        ),
+        expected_type: result_type.clone(),
+        actual_type: result_type.clone(),
+        is_valid,
+        error_message: if !is_valid {
+            Some(format!("Operación {} no válida para tipo {}", operator, operand_type))
+        } else {
+            None
+        },
+    };
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Analizando operación unaria: {} (operando: {})", operator, operand_type),
+        node_type: "UnaryOp".to_string(),
+        action: "Verificar tipo de operando".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", result_type)),
+        error: None,
+        ..Default::default()
+    }, vec![type_check]);
+    result_type
+}
+
+/// Analyzes a binary operation and its operands, returning the result's type
+fn analyze_binary_op(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    let ASTNode::BinaryOp { left, right, .. } = node else { return UNKNOWN_TYPE.to_string() };
+    let slot = trace.reserve();
+    let left_type = analyze_node(left, trace, symbol_table);
+    let right_type = analyze_node(right, trace, symbol_table);
+    check_binary_op(node, &left_type, &right_type, slot, trace)
+}
+
+/// Checks a binary operation's operand types, returning the result's type
+fn check_binary_op(node: &ASTNode, left_type: &str, right_type: &str, slot: usize, trace: &mut Trace) -> String {
+    let ASTNode::BinaryOp { left, operator, right } = node else { return UNKNOWN_TYPE.to_string() };
+    let is_integer_operator = matches!(operator.as_str(), "%" | "&" | "|" | "^" | "&^" | "<<" | ">>");
+    let result_type = match operator.as_str() {
+        "+" | "-" | "*" | "/" => {
+            if left_type == "float64" || right_type == "float64" {
+                "float64".to_string()
+            } else {
+                "int".to_string()
+            }
+        },
+        "%" | "&" | "|" | "^" | "&^" => {
+            let (left_type, right_type) = integer_operand_types(left, left_type, right, right_type);
+            integer_result(&left_type, &right_type)
+        },
+        "<<" | ">>" => integer_result(left_type, right_type), // Integer-only operations
+        "==" | "!=" | "<" | ">" | "<=" | ">=" => "bool".to_string(), // Comparison operations
+        "&&" | "||" => "bool".to_string(), // Logical operations
+        _ => UNKNOWN_TYPE.to_string(),
+    };
+    
+    // Add type check
+    let is_valid = match operator.as_str() {
+        // Nothing can be checked against an operand whose type is unknown
+        _ if left_type == UNKNOWN_TYPE || right_type == UNKNOWN_TYPE => true,
+        "+" | "-" | "*" | "/" => {
+            left_type == "int" || left_type == "float64" || left_type == "float32" ||
+            right_type == "int" || right_type == "float64" || right_type == "float32"
+        },
+        // Shifts only need integer operands; the rest also need matching types
+        "<<" | ">>" => is_integer_type(left_type) && is_integer_type(right_type),
+        "%" | "&" | "|" | "^" | "&^" => {
+            let (left_type, right_type) = integer_operand_types(left, left_type, right, right_type);
+            is_integer_type(&left_type) && left_type == right_type
+        },
+        "==" | "!=" | "<" | ">" | "<=" | ">=" => {
+            left_type == "int" || left_type == "float64" || left_type == "float32" || left_type == "string" || left_type == "bool" ||
+            right_type == "int" || right_type == "float64" || right_type == "float32" || right_type == "string" || right_type == "bool"
+        },
+        "&&" | "||" => {
+            left_type == "bool" && right_type == "bool"
+        },
+        _ => true,
+    };
+    
+    let type_check = TypeCheck {
+        expression: format!("{} {} {}",
+            match &**left {
+                ASTNode::Identifier { name } => name.clone(),
+                ASTNode::Number { value, .. } => value.clone(),
+                _ => "expr".to_string(),
+            },
+            operator,
+            match &**right {
+                ASTNode::Identifier { name } => name.clone(),
+                ASTNode::Number { value, .. } => value.clone(),
+                _ => "expr".to_string(),
+            }
+        ),
+        expected_type: result_type.clone(),
+        actual_type: result_type.clone(),
+        is_valid,
+        error_message: if !is_valid {
+            // Two integers of different types are a mismatch, not a misuse of the operator
+            if is_integer_operator && !(is_integer_type(left_type) && is_integer_type(right_type)) {
+                Some(format!("El operador {} solo admite operandos enteros: {} {} {}", operator, left_type, operator, right_type))
+            } else {
+                Some(format!("Tipos incompatibles: {} {} {}", left_type, operator, right_type))
+            }
+        } else {
+            None
+        },
+    };
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Analizando operación binaria: {} ({} {} {})", operator, left_type, operator, right_type),
+        node_type: "BinaryOp".to_string(),
+        action: "Verificar tipos de operandos".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", result_type)),
+        error: None,
+        ..Default::default()
+    }, vec![type_check]);
+    result_type
+}
+
+/// Adds an assigned variable to the symbol table, fills in its type if it was unknown, or checks
+/// the value against it, writing the step into the place reserved for it
+fn record_assignment(name: &str, right_type: String, slot: usize, trace: &mut Trace,
+                     symbol_table: &mut Vec<SymbolInfo>) {
+    // Add to symbol table if not exists
+    let Some(symbol) = symbol_table.iter_mut().find(|sym| sym.name == name) else {
+        symbol_table.push(SymbolInfo {
+            name: name.to_string(),
+            symbol_type: "Variable".to_string(),
+            data_type: right_type.clone(),
+            scope: "Global".to_string(),
+            line: 1,
+        });
+        
+        trace.fill(slot, SemanticStep {
+            description: format!("Variable '{}' agregada a tabla de símbolos con tipo {}", name, right_type),
+            node_type: "Identifier".to_string(),
+            action: "Agregar a tabla de símbolos".to_string(),
+            symbol_added: Some(name.to_string()),
+            type_check: Some(right_type),
+            error: None,
+            ..Default::default()
+        }, Vec::new());
+        return;
+    };
+    
+    // Update existing symbol type if needed
+    if symbol.data_type == UNKNOWN_TYPE {
+        symbol.data_type = right_type.clone();
+        trace.fill(slot, SemanticStep {
+            description: format!("Tipo de variable '{}' actualizado a {}", name, right_type),
+            node_type: "Identifier".to_string(),
+            action: "Actualizar tipo en tabla de símbolos".to_string(),
+            symbol_added: None,
+            type_check: Some(right_type),
+            error: None,
+            ..Default::default()
+        }, Vec::new());
+    } else if !is_assignable_type(&symbol.data_type, &right_type) {
+        // A variable keeps the type it was first given
+        let mut type_checks = Vec::new();
+        let error = type_mismatch(&mut type_checks, name.to_string(), &symbol.data_type, &right_type,
+            format!("Tipos incompatibles: {} = {}", symbol.data_type, right_type));
+        trace.fill(slot, SemanticStep {
+            description: format!("Asignación a variable '{}' (tipo: {}) = {}", name, symbol.data_type, right_type),
+            node_type: "Identifier".to_string(),
+            action: "Verificar tipo de la variable".to_string(),
+            symbol_added: None,
+            type_check: Some(symbol.data_type.clone()),
+            error: Some(error),
+            ..Default::default()
+        }, type_checks);
+    }
+}
+
+/// Analyzes `receiver.method(args)`, its receiver and its arguments, returning the method's result type
+fn analyze_method_call(node: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> String {
+    let ASTNode::FunctionCall { callee, arguments } = node else { return UNKNOWN_TYPE.to_string() };
+    let ASTNode::FieldAccess { target, .. } = &**callee else { return UNKNOWN_TYPE.to_string() };
+    let slot = trace.reserve();
+    let receiver_type = analyze_node(target, trace, symbol_table);
+    let argument_types: Vec<String> = arguments.iter()
+        .map(|argument| analyze_node(argument, trace, symbol_table))
+        .collect();
+    check_method_call(node, &receiver_type, &argument_types, slot, trace)
+}
+
+/// Resolves a method call against the methods of the receiver's type, returning the method's result type
+fn check_method_call(node: &ASTNode, receiver_type: &str, argument_types: &[String], slot: usize, trace: &mut Trace) -> String {
+    let ASTNode::FunctionCall { callee, arguments } = node else { return UNKNOWN_TYPE.to_string() };
+    let ASTNode::FieldAccess { field, .. } = &**callee else { return UNKNOWN_TYPE.to_string() };
+    let method = resolve_method(receiver_type, field);
+    let result_type = method
+        .map(|(_, result)| result.to_string())
+        .unwrap_or_else(|| UNKNOWN_TYPE.to_string());
+    let mut type_checks = Vec::new();
+    let mut errors = Vec::new();
+    match method {
+        _ if receiver_type == UNKNOWN_TYPE => {},
+        None => errors.push(type_mismatch(&mut type_checks, print_ast(callee), receiver_type, UNKNOWN_TYPE,
+            format!("El tipo {} no tiene el método '{}'", receiver_type, field))),
+        Some((parameters, _)) if parameters.len() != arguments.len() => {
+            errors.push(format!("{} espera {} argumentos, recibió {}", field, parameters.len(), arguments.len()));
+        },
+        Some((parameters, _)) => {
+            for ((argument, argument_type), parameter) in arguments.iter().zip(argument_types).zip(parameters) {
+                if !is_assignable_type(parameter, argument_type) {
+                    errors.push(type_mismatch(&mut type_checks, print_ast(argument), parameter, argument_type,
+                        format!("El argumento de {} debe ser {}, no {}", field, parameter, argument_type)));
+                }
+            }
+        },
+    }
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Llamada a método: {} (receptor: {}, resultado: {})", print_ast(node), receiver_type, result_type),
+        node_type: "FunctionCall".to_string(),
+        action: "Resolver método por tipo del receptor".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", result_type)),
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+        ..Default::default()
+    }, type_checks);
+    result_type
+}
+
+/// Analyzes the target of a store into `a[i]` or `p.X`, returning its type and, for an
+/// element, the type of the value it belongs to
+fn analyze_store_target(target: &ASTNode, trace: &mut Trace, symbol_table: &mut Vec<SymbolInfo>) -> (String, String) {
+    match target {
+        ASTNode::Index { .. } => analyze_index(target, trace, symbol_table),
+        _ => (analyze_node(target, trace, symbol_table), UNKNOWN_TYPE.to_string()),
+    }
+}
+
+/// Checks a store into `a[i]` or `p.X`: strings are immutable and the value must fit the element or field type
+fn analyze_element_assignment(target: &ASTNode, element: &str, container_type: &str, value_type: &str, slot: usize,
+                              trace: &mut Trace) {
+    let kind = if let ASTNode::FieldAccess { .. } = target { "campo" } else { "elemento" };
+    let mut type_checks = Vec::new();
+    let error = if container_type == "string" {
+        Some(type_mismatch(&mut type_checks, print_ast(target), "arreglo o slice", "string",
+            format!("No se puede asignar a {}: los strings son inmutables", print_ast(target))))
+    } else if !is_assignable_type(element, value_type) {
+        Some(type_mismatch(&mut type_checks, print_ast(target), element, value_type,
+            format!("No se puede asignar {} a un {} de tipo {}", value_type, kind, element)))
+    } else {
+        None
+    };
+    
+    trace.fill(slot, SemanticStep {
+        description: format!("Asignación a {}: {} (tipo: {}) = {}", kind, print_ast(target), element, value_type),
+        node_type: target.node_type().to_string(),
+        action: "Verificar tipo del elemento".to_string(),
+        symbol_added: None,
+        type_check: Some(element.to_string()),
+        error,
+        ..Default::default()
+    }, type_checks);
+}
+
+/// Checks the elements of a map literal, given the key and value type of each: all keyed, with
+/// keys and values of the map's types and no repeated constant keys
+fn check_map_literal(literal_type: &str, key_type: &str, value_type: &str, elements: &[ASTNode],
+                     element_types: &[(String, String)], type_checks: &mut Vec<TypeCheck>) -> Vec<String> {
+    let mut errors = Vec::new();
+    if !is_comparable_type(key_type) {
+        errors.push(format!("Tipo de clave inválido en {}: {} no es comparable", literal_type, key_type));
+    }
+    
+    let mut seen = Vec::new();
+    for (item, (actual_key, actual_value)) in elements.iter().zip(element_types) {
+        let ASTNode::KeyValue { key, value } = item else {
+            errors.push(format!("Los literales de {} requieren pares clave: valor, no {}", literal_type, print_ast(item)));
+            continue;
+        };
+        for (node, expected, actual, role) in [(key, key_type, actual_key, "clave"), (value, value_type, actual_value, "valor")] {
+            if !is_assignable_type(expected, actual) {
+                errors.push(type_mismatch(type_checks, print_ast(node), expected, actual,
+                    format!("No se puede usar {} como {} de tipo {} en {}", actual, role, expected, literal_type)));
+            }
+        }
+        if let ASTNode::Number { .. } | ASTNode::String { .. } | ASTNode::Char { .. } | ASTNode::Boolean { .. } = &**key {
+            let key_text = print_ast(key);
+            if seen.contains(&key_text) {
+                errors.push(format!("Clave duplicada {} en el literal de {}", key_text, literal_type));
+            } else {
+                seen.push(key_text);
+            }
+        }
+    }
+    errors
+}
+
+/// Checks the elements of a struct literal, given the value type of each, either all keyed by
+/// field name or all positional
+fn check_struct_literal(literal_type: &str, fields: &[(&str, &str)], elements: &[ASTNode],
+                        element_types: &[(String, String)], type_checks: &mut Vec<TypeCheck>) -> Vec<String> {
+    let keyed = elements.iter().filter(|item| matches!(item, ASTNode::KeyValue { .. })).count();
+    let mut errors = Vec::new();
+    let mut check_value = |field: &str, field_type: &str, value: &ASTNode, value_type: &str, errors: &mut Vec<String>| {
+        if !is_assignable_type(field_type, value_type) {
+            errors.push(type_mismatch(type_checks, print_ast(value), field_type, value_type,
+                format!("No se puede usar {} como {} en el campo '{}' de {}", value_type, field_type, field, literal_type)));
+        }
+    };
+    
+    if keyed == 0 {
+        if !elements.is_empty() && elements.len() != fields.len() {
+            errors.push(format!("{} tiene {} campos pero el literal da {} valores", literal_type, fields.len(), elements.len()));
+        }
+        for ((value, (_, value_type)), (field, field_type)) in elements.iter().zip(element_types).zip(fields) {
+            check_value(field, field_type, value, value_type, &mut errors);
+        }
+    } else if keyed < elements.len() {
+        errors.push(format!("No se pueden mezclar campos con y sin nombre en {}", literal_type));
+    } else {
+        let mut seen: Vec<&str> = Vec::new();
+        for (item, (_, value_type)) in elements.iter().zip(element_types) {
+            let ASTNode::KeyValue { key, value } = item else { continue };
+            let field = match &**key {
+                ASTNode::Identifier { name } => fields.iter().find(|(field, _)| field == name),
+                _ => None,
+            };
+            match field {
+                None => errors.push(format!("El campo '{}' no existe en {}", print_ast(key), literal_type)),
+                Some((name, _)) if seen.contains(name) => errors.push(format!("Campo '{}' repetido en el literal", name)),
+                Some((name, field_type)) => {
+                    seen.push(name);
+                    check_value(name, field_type, value, value_type, &mut errors);
+                },
+            }
+        }
+    }
+    errors
+}
+
+/// Value of an integer literal index, or of a negated one
+fn constant_index(bound: &ASTNode) -> Option<i128> {
+    match bound {
+        ASTNode::Number { value, is_float: false } => match NumericValue::from_literal(value, false)? {
+            NumericValue::Integer(n) => Some(n as i128),
+            _ => None,
+        },
+        ASTNode::UnaryOp { operator, operand } if operator == "-" => constant_index(operand).map(|n| -n),
+        ASTNode::Parenthesized { expression } => constant_index(expression),
+        _ => None,
+    }
+}
+
+
+/// Checks an index or slice bound of `bound_type`: it must be an integer and, when constant,
+/// non-negative and within a fixed array's length (`inclusive` allows the length itself)
+fn check_index(bound: &ASTNode, bound_type: &str, container_type: &str, inclusive: bool,
+               type_checks: &mut Vec<TypeCheck>) -> Vec<String> {
+    let mut errors = Vec::new();
+    if bound_type != UNKNOWN_TYPE && !is_integer_type(bound_type) {
+        errors.push(type_mismatch(type_checks, print_ast(bound), "int", bound_type,
+            format!("El índice debe ser entero, no {}", bound_type)));
+    }
+    if let Some(n) = constant_index(bound) {
+        let limit = array_length(container_type).map(|length| length as i128 + i128::from(inclusive));
+        if n < 0 {
+            errors.push(type_mismatch(type_checks, print_ast(bound), "índice >= 0", &n.to_string(),
+                format!("Índice negativo: {}", n)));
+        } else if limit.is_some_and(|limit| n >= limit) {
+            errors.push(type_mismatch(type_checks, print_ast(bound), container_type, &n.to_string(),
+                format!("Índice {} fuera de rango para {}", n, container_type)));
+        }
+    }
+    errors
+}
+
+/// Records a numeric literal, flagging integer constants that overflow `int`, and returns its type
+fn analyze_number(value: &str, is_float: bool, negated: bool, trace: &mut Trace) -> String {
+    let go_type = if is_float { "float64" } else { "int" };
+    let decoded = NumericValue::from_literal(value, is_float);
+    let overflows = match decoded {
+        Some(NumericValue::Integer(n)) => n > i64::MAX as u64 + u64::from(negated),
+        Some(NumericValue::BigInteger) => true,
+        _ => false,
+    };
+    let shown = match decoded {
+        Some(NumericValue::Integer(n)) if n.to_string() != value => format!("{} = {}", value, n),
+        _ => value.to_string(),
+    };
+    let error = overflows.then(|| {
+        format!("La constante {}{} desborda int", if negated { "-" } else { "" }, value)
+    });
+    
+    let mut type_checks = Vec::new();
+    if overflows {
+        type_checks.push(TypeCheck {
+            expression: value.to_string(),
+            expected_type: "int".to_string(),
+            actual_type: "untyped int".to_string(),
+            is_valid: false,
+            error_message: error.clone(),
+        });
+    }
+    
+    trace.push(SemanticStep {
+        description: format!("Literal numérico: {} (tipo: {})", shown, go_type),
+        node_type: "Number".to_string(),
+        action: "Verificar tipo numérico".to_string(),
+        symbol_added: None,
+        type_check: Some(go_type.to_string()),
+        error,
+        ..Default::default()
+    }, type_checks);
+    go_type.to_string()
+}
+
+/// Checks `x++`, `x--` and `x op= value`: the target must be a declared variable, element or field
+/// whose type the operator accepts, and the value must keep that type
+fn analyze_update(target: &ASTNode, operator: &str, value: Option<&ASTNode>, trace: &mut Trace,
+                  symbol_table: &mut Vec<SymbolInfo>) {
+    // `+=` applies `+`, `<<=` applies `<<`, and `++` / `--` apply `+` / `-`
+    let base_operator = match value {
+        Some(_) => operator.trim_end_matches('='),
+        None => &operator[..1],
+    };
+    let slot = trace.reserve();
+    let target_type = match target {
+        ASTNode::Identifier { name } => symbol_table.iter()
+            .find(|sym| sym.name == *name)
+            .map(|sym| sym.data_type.clone())
+            .unwrap_or_else(|| UNKNOWN_TYPE.to_string()),
+        ASTNode::Index { .. } | ASTNode::FieldAccess { .. } => analyze_node(target, trace, symbol_table),
+        _ => UNKNOWN_TYPE.to_string(),
+    };
+    let value_type = value.map(|v| analyze_node(v, trace, symbol_table));
+    let target_text = print_ast(target);
+    let accepted: &[&str] = match base_operator {
+        "+" if value.is_some() => &["int", "float64", "rune", "byte", "string"],
+        "+" | "-" | "*" | "/" => &["int", "float64", "rune", "byte"],
+        _ => &["int", "rune", "byte"],
+    };
+    
+    let error = match target {
+        ASTNode::Identifier { name } if !symbol_table.iter().any(|sym| sym.name == *name) => {
+            Some(format!("Variable '{}' no está declarada", name))
+        },
+        ASTNode::Identifier { .. } | ASTNode::Index { .. } | ASTNode::FieldAccess { .. } => {
+            if !accepted.contains(&target_type.as_str()) {
+                Some(format!("No se puede aplicar {} a '{}' de tipo {}", operator, target_text, target_type))
+            } else {
+                match value_type.as_deref() {
+                    Some(UNKNOWN_TYPE) | None => None,
+                    Some(v) if matches!(base_operator, "<<" | ">>") => {
+                        (!is_integer_type(v)).then(|| format!("El desplazamiento {} requiere un entero, no {}", operator, v))
+                    },
+                    // Integer constants may be added to float variables, as with untyped constants in Go
+                    Some(v) if v == target_type || (target_type == "float64" && v == "int") => None,
+                    // ...and to variables of any integer type, so `r += 1` keeps `r` a rune
+                    Some(_) if value.is_some_and(is_untyped_integer) && target_type != "string" => None,
+                    Some(v) => Some(format!("Tipos incompatibles: {} {} {}", target_type, operator, v)),
+                }
+            }
+        },
+        _ => Some(format!("No se puede aplicar {} a una expresión que no es una variable", operator)),
+    };
+    
+    let (description, expression) = match &value_type {
+        Some(v) => (
+            format!("Analizando asignación compuesta: {} {} ({} {} {})", target_text, operator, target_type, operator, v),
+            format!("{} {} expr", target_text, operator),
+        ),
+        None => (
+            format!("Analizando {}: {} (tipo: {})", operator, target_text, target_type),
+            format!("{}{}", target_text, operator),
+        ),
+    };
+    let type_check = TypeCheck {
+        expression,
+        expected_type: target_type.clone(),
+        actual_type: value_type.unwrap_or_else(|| target_type.clone()),
+        is_valid: error.is_none(),
+        error_message: error.clone(),
+    };
+    
+    trace.fill(slot, SemanticStep {
+        description,
+        node_type: if value.is_some() { "CompoundAssignment" } else { "IncDec" }.to_string(),
+        action: "Verificar variable asignable y tipo numérico".to_string(),
+        symbol_added: None,
+        type_check: Some(format!("Resultado: {}", target_type)),
+        error,
+        ..Default::default()
+    }, vec![type_check]);
+}
@@ -0,0 +1,118 @@
+//! Replays the fuzz seed corpus and every saved crash input through the pipeline.
+//!
+//! When a fuzzer finds a crash, copy the input from `fuzz/artifacts/<target>/`
+//! into `fuzz/regressions/` so it keeps being checked here.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use compiler_core::ast::ASTNode;
+use compiler_core::graphviz::GraphvizRenderer;
+use compiler_core::lexer::Lexer;
+use compiler_core::parser::{stack_size_for, ParseError, Parser, MAX_NESTING_DEPTH};
+use compiler_core::printer::print_ast;
+use compiler_core::semantic::analyze;
+
+fn fuzz_dir(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../fuzz").join(name)
+}
+
+fn inputs(dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut inputs: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("cannot read {}: {e}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .map(|path| {
+            let bytes = fs::read(&path).unwrap();
+            (path, String::from_utf8_lossy(&bytes).into_owned())
+        })
+        .collect();
+    inputs.sort();
+    assert!(!inputs.is_empty(), "no inputs in {}", dir.display());
+    inputs
+}
+
+/// Mirrors the `pipeline` fuzz target, on a thread with the stack the server gives a program this long
+fn run_pipeline(source: &str) {
+    let source = source.to_string();
+    std::thread::Builder::new()
+        .stack_size(stack_size_for(source.len()))
+        .spawn(move || {
+            let _ = Lexer::new(&source).tokenize_all();
+            if let Ok(ast) = Parser::new(&source).and_then(|mut parser| parser.parse()) {
+                let _ = analyze(&ast);
+                let _ = GraphvizRenderer::new().render_to_dot(&ast);
+                let _ = print_ast(&ast);
+            }
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn seed_corpus_compiles() {
+    for (path, source) in inputs(&fuzz_dir("seeds")) {
+        let result = Parser::new(&source).and_then(|mut parser| parser.parse());
+        assert!(result.is_ok(), "{}: {:?}", path.display(), result.err());
+        run_pipeline(&source);
+    }
+}
+
+#[test]
+fn regressions_do_not_crash() {
+    for (_, source) in inputs(&fuzz_dir("regressions")) {
+        run_pipeline(&source);
+    }
+}
+
+#[test]
+fn nesting_limit_is_reported() {
+    let source = format!("{}1{}", "(".repeat(MAX_NESTING_DEPTH), ")".repeat(MAX_NESTING_DEPTH));
+    let result = Parser::new(&source).and_then(|mut parser| parser.parse());
+    assert!(matches!(result, Err(ParseError::NestingTooDeep { .. })));
+}
+
+#[test]
+fn nesting_below_limit_is_accepted() {
+    let depth = MAX_NESTING_DEPTH - 1;
+    let source = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+    run_pipeline(&source);
+    assert!(Parser::new(&source).and_then(|mut parser| parser.parse()).is_ok());
+}
+
+/// Chains are not nesting: they are as deep as they are long, and the stack grows with the program
+#[test]
+fn long_chains_compile() {
+    for link in ["+1", "||b", ".b", "[0]", "[1:]", "()"] {
+        let source = format!("x = a{}", link.repeat(1000));
+        assert!(Parser::new(&source).and_then(|mut parser| parser.parse()).is_ok(), "{link}");
+        run_pipeline(&source);
+    }
+}
+
+/// Stack a tokio blocking thread gets; the server runs the pipeline on those
+const WORKER_STACK_SIZE: usize = 2 << 20;
+
+/// Far deeper than the parser allows, so the margin under the limit stays visible
+#[test]
+fn analysis_of_a_long_chain_fits_a_worker_stack() {
+    let depth = 8 * MAX_NESTING_DEPTH;
+    let mut chain = ASTNode::Identifier { name: "a".to_string() };
+    for _ in 0..depth {
+        chain = ASTNode::BinaryOp {
+            left: Box::new(chain),
+            operator: "+".to_string(),
+            right: Box::new(ASTNode::Number { value: "1".to_string(), is_float: false }),
+        };
+    }
+    let program = ASTNode::Program { statements: vec![ASTNode::ExpressionStatement { expression: Box::new(chain) }] };
+
+    let analysis = std::thread::Builder::new()
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || analyze(&program))
+        .unwrap()
+        .join()
+        .unwrap();
+    let binary_steps = analysis.steps.iter().filter(|step| step.node_type == "BinaryOp").count();
+    assert_eq!(binary_steps, depth);
+}
@@ -0,0 +1,116 @@
+//! Golden-file snapshots for every compiler stage.
+//!
+//! Each directory under `tests/golden/` holds an `input.src` program and the
+//! expected output of each stage next to it:
+//!
+//! - `tokens.json`: the lexer's tokens, plus the lexer error if there is one
+//! - `ast.json`: the parsed AST, or the parse error
+//! - `ast.dot`: the Graphviz rendering of the AST
+//! - `semantic.json`: the semantic analysis steps, symbols and type checks
+//!
+//! Stages after a failed parse have no expected file. Run with `BLESS=1` to
+//! write the current output as the new expectation:
+//!
+//! ```text
+//! BLESS=1 cargo test -p compiler_core --test golden
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use compiler_core::graphviz::GraphvizRenderer;
+use compiler_core::lexer::{Lexer, TokenType};
+use compiler_core::parser::Parser;
+use compiler_core::semantic::analyze;
+use serde_json::json;
+use similar::TextDiff;
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn to_json(value: serde_json::Value) -> String {
+    serde_json::to_string_pretty(&value).unwrap() + "\n"
+}
+
+/// Runs the pipeline and returns each stage's output keyed by file name
+fn run_stages(source: &str) -> Vec<(&'static str, Option<String>)> {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    let lex_error = loop {
+        match lexer.next_token() {
+            Ok(TokenType::EOF) => break None,
+            Ok(token) => tokens.push(token),
+            Err(e) => break Some(e.to_string()),
+        }
+    };
+    let tokens_json = to_json(json!({ "tokens": tokens, "error": lex_error }));
+
+    let parsed = Parser::new(source).and_then(|mut parser| parser.parse());
+    let (ast_json, dot, semantic) = match parsed {
+        Ok(ast) => (
+            to_json(json!({ "ast": ast })),
+            Some(GraphvizRenderer::new().render_to_dot(&ast)),
+            Some(to_json(json!(analyze(&ast)))),
+        ),
+        Err(e) => (to_json(json!({ "error": e.to_string() })), None, None),
+    };
+
+    vec![
+        ("tokens.json", Some(tokens_json)),
+        ("ast.json", Some(ast_json)),
+        ("ast.dot", dot),
+        ("semantic.json", semantic),
+    ]
+}
+
+/// Compares one stage with its expected file, or rewrites it when blessing
+fn check_stage(case: &Path, file: &str, actual: Option<String>, bless: bool) -> Option<String> {
+    let path = case.join(file);
+    let expected = fs::read_to_string(&path).ok();
+    if expected == actual {
+        return None;
+    }
+    if bless {
+        match &actual {
+            Some(content) => fs::write(&path, content).unwrap(),
+            None => fs::remove_file(&path).unwrap(),
+        }
+        return None;
+    }
+    let expected = expected.unwrap_or_default();
+    let actual = actual.unwrap_or_default();
+    let diff = TextDiff::from_lines(&expected, &actual)
+        .unified_diff()
+        .header("expected", "actual")
+        .to_string();
+    Some(format!("{}:\n{}", path.display(), diff))
+}
+
+#[test]
+fn golden_snapshots() {
+    let bless = std::env::var_os("BLESS").is_some();
+    let mut cases: Vec<_> = fs::read_dir(golden_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    cases.sort();
+    assert!(!cases.is_empty(), "no golden cases found");
+
+    let mut failures = Vec::new();
+    for case in &cases {
+        let source = fs::read_to_string(case.join("input.src"))
+            .unwrap_or_else(|e| panic!("{}: {e}", case.display()));
+        for (file, actual) in run_stages(&source) {
+            failures.extend(check_stage(case, file, actual, bless));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} golden file(s) differ; rerun with BLESS=1 to accept:\n\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
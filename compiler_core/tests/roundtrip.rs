@@ -0,0 +1,228 @@
+//! Property tests tying the lexer, parser and printer together
+
+use compiler_core::ast::{ASTNode, StructField};
+use compiler_core::lexer::{Keyword, Lexer};
+use compiler_core::parser::{Parser, COMPOUND_ASSIGNMENT_OPERATORS};
+use compiler_core::printer::print_ast;
+use proptest::prelude::*;
+
+// Binding strength of each node as the parser sees it, lowest first
+const PREC_ASSIGNMENT: u8 = 0;
+const PREC_UNARY: u8 = 7;
+const PREC_PRIMARY: u8 = 8;
+
+fn binary_precedence(operator: &str) -> u8 {
+    match operator {
+        "||" => 1,
+        "&&" => 2,
+        "==" | "!=" => 3,
+        "<" | ">" | "<=" | ">=" => 4,
+        "+" | "-" | "|" | "^" => 5,
+        "*" | "/" | "%" | "<<" | ">>" | "&" | "&^" => 6,
+        other => panic!("no precedence for {other}"),
+    }
+}
+
+fn precedence(node: &ASTNode) -> u8 {
+    match node {
+        ASTNode::Assignment { .. } => PREC_ASSIGNMENT,
+        ASTNode::BinaryOp { operator, .. } => binary_precedence(operator),
+        ASTNode::UnaryOp { .. } => PREC_UNARY,
+        _ => PREC_PRIMARY,
+    }
+}
+
+/// Wraps `node` in parentheses when it binds looser than its position requires
+fn operand(node: ASTNode, min_prec: u8) -> Box<ASTNode> {
+    if precedence(&node) < min_prec {
+        Box::new(ASTNode::Parenthesized { expression: Box::new(node) })
+    } else {
+        Box::new(node)
+    }
+}
+
+fn identifier() -> impl Strategy<Value = String> {
+    "[a-zA-Z_][a-zA-Z0-9_]{0,6}".prop_filter("keywords are not identifiers", |name| {
+        name != "true" && name != "false" && Keyword::lookup(name).is_none()
+    })
+}
+
+/// Array, slice and map types such as `int`, `[]string`, `[3][]rune` or `map[string][]int`
+fn type_name() -> impl Strategy<Value = String> {
+    let prefix = prop_oneof![
+        Just("[]".to_string()),
+        (0u64..100).prop_map(|length| format!("[{}]", length)),
+        identifier().prop_map(|key| format!("map[{}]", key)),
+    ];
+    (prop::collection::vec(prefix, 0..3), identifier()).prop_map(|(prefixes, name)| prefixes.concat() + &name)
+}
+
+/// Variables, indexed elements and fields, optionally parenthesized, are the only valid assignment targets
+fn assignment_target() -> impl Strategy<Value = ASTNode> {
+    let selector = prop_oneof![
+        leaf().prop_map(|index| Some(Err(index))),
+        identifier().prop_map(|field| Some(Ok(field))),
+        Just(None),
+    ];
+    (identifier(), selector, any::<bool>()).prop_map(|(name, selector, parenthesized)| {
+        let variable = Box::new(ASTNode::Identifier { name });
+        let target = match selector {
+            Some(Ok(field)) => ASTNode::FieldAccess { target: variable, field },
+            Some(Err(index)) => ASTNode::Index { target: variable, index: Box::new(index) },
+            None => *variable,
+        };
+        if parenthesized {
+            ASTNode::Parenthesized { expression: Box::new(target) }
+        } else {
+            target
+        }
+    })
+}
+
+fn leaf() -> impl Strategy<Value = ASTNode> {
+    prop_oneof![
+        "0|[1-9][0-9]{0,5}|[1-9](_?[0-9]){1,5}|0[xX][0-9a-fA-F]{1,6}|0o[0-7]{1,6}|0b[01]{1,8}"
+            .prop_map(|value| ASTNode::Number { value, is_float: false }),
+        "(0|[1-9][0-9]{0,2})\\.[0-9]{1,3}".prop_map(|value| ASTNode::Number { value, is_float: true }),
+        prop::collection::vec(any::<char>(), 0..8)
+            .prop_map(|chars| ASTNode::String { value: chars.into_iter().collect() }),
+        any::<char>().prop_map(|value| ASTNode::Char { value }),
+        any::<bool>().prop_map(|value| ASTNode::Boolean { value }),
+        identifier().prop_map(|name| ASTNode::Identifier { name }),
+    ]
+}
+
+/// Generates expressions whose shape the parser can reproduce exactly
+fn expression() -> impl Strategy<Value = ASTNode> {
+    leaf().prop_recursive(6, 48, 4, |inner| {
+        prop_oneof![
+            (
+                inner.clone(),
+                prop::sample::select(vec![
+                    "||", "&&", "==", "!=", "<", ">", "<=", ">=", "+", "-", "|", "^", "*", "/", "%", "<<", ">>", "&", "&^",
+                ]),
+                inner.clone(),
+            )
+                .prop_map(|(left, operator, right)| {
+                    let prec = binary_precedence(operator);
+                    ASTNode::BinaryOp {
+                        left: operand(left, prec),
+                        operator: operator.to_string(),
+                        right: operand(right, prec + 1),
+                    }
+                }),
+            (prop::sample::select(vec!["-", "!", "^"]), inner.clone()).prop_map(|(operator, node)| {
+                ASTNode::UnaryOp {
+                    operator: operator.to_string(),
+                    operand: operand(node, PREC_UNARY),
+                }
+            }),
+            (assignment_target(), inner.clone()).prop_map(|(left, right)| ASTNode::Assignment {
+                left: Box::new(left),
+                right: operand(right, PREC_ASSIGNMENT),
+            }),
+            (inner.clone(), prop::collection::vec(inner.clone(), 0..4))
+                .prop_map(|(callee, arguments)| ASTNode::FunctionCall { callee: operand(callee, PREC_PRIMARY), arguments }),
+            (
+                prop::sample::select(vec!["[]", "[4]", "map[string]", ""]),
+                type_name(),
+                prop::collection::vec((prop::option::of(inner.clone()), inner.clone()), 0..4),
+            )
+                .prop_map(|(prefix, element, elements)| ASTNode::CompositeLiteral {
+                    literal_type: format!("{}{}", prefix, element),
+                    elements: elements.into_iter()
+                        .map(|(key, value)| match key {
+                            Some(key) => ASTNode::KeyValue { key: Box::new(key), value: Box::new(value) },
+                            None => value,
+                        })
+                        .collect(),
+                }),
+            (inner.clone(), identifier()).prop_map(|(target, field)| ASTNode::FieldAccess {
+                // `1.X` would lex as the float `1.` followed by `X`
+                target: match target {
+                    ASTNode::Number { .. } => Box::new(ASTNode::Parenthesized { expression: Box::new(target) }),
+                    _ => operand(target, PREC_PRIMARY),
+                },
+                field,
+            }),
+            (inner.clone(), inner.clone()).prop_map(|(target, index)| ASTNode::Index {
+                target: operand(target, PREC_PRIMARY),
+                index: Box::new(index),
+            }),
+            (inner.clone(), prop::option::of(inner.clone()), prop::option::of(inner.clone()))
+                .prop_map(|(target, low, high)| ASTNode::Slice {
+                    target: operand(target, PREC_PRIMARY),
+                    low: low.map(Box::new),
+                    high: high.map(Box::new),
+                }),
+            inner.prop_map(|node| ASTNode::Parenthesized { expression: Box::new(node) }),
+        ]
+    })
+}
+
+fn statement() -> impl Strategy<Value = ASTNode> {
+    let target = || identifier().prop_map(|name| Box::new(ASTNode::Identifier { name }));
+    prop_oneof![
+        4 => expression().prop_map(|expression| ASTNode::ExpressionStatement { expression: Box::new(expression) }),
+        1 => (target(), prop::sample::select(vec!["++", "--"]))
+            .prop_map(|(target, operator)| ASTNode::IncDec { target, operator: operator.to_string() }),
+        1 => (target(), prop::sample::select(COMPOUND_ASSIGNMENT_OPERATORS.to_vec()), expression())
+            .prop_map(|(target, operator, value)| ASTNode::CompoundAssignment {
+                target,
+                operator: operator.to_string(),
+                value: operand(value, PREC_ASSIGNMENT + 1),
+            }),
+        1 => type_declaration(),
+        1 => (prop::collection::vec(assignment_target(), 2..4), prop::collection::vec(expression(), 1..4))
+            .prop_map(|(targets, values)| ASTNode::MultipleAssignment {
+                targets,
+                values: values.into_iter().map(|value| *operand(value, PREC_ASSIGNMENT + 1)).collect(),
+            }),
+    ]
+}
+
+fn type_declaration() -> impl Strategy<Value = ASTNode> {
+    let field = (identifier(), type_name()).prop_map(|(name, field_type)| StructField { name, field_type });
+    (identifier(), prop::collection::vec(field, 0..4)).prop_map(|(name, fields)| ASTNode::TypeDeclaration { name, fields })
+}
+
+fn program() -> impl Strategy<Value = ASTNode> {
+    prop::collection::vec(statement(), 0..5).prop_map(|statements| ASTNode::Program { statements })
+}
+
+fn parse(source: &str) -> ASTNode {
+    Parser::new(source)
+        .and_then(|mut parser| parser.parse())
+        .unwrap_or_else(|e| panic!("failed to re-parse {source:?}: {e}"))
+}
+
+proptest! {
+    #[test]
+    fn print_then_parse_is_identity(ast in program()) {
+        let source = print_ast(&ast);
+        prop_assert_eq!(parse(&source), ast, "source: {}", source);
+    }
+
+    #[test]
+    fn printing_is_stable(ast in program()) {
+        let source = print_ast(&ast);
+        prop_assert_eq!(print_ast(&parse(&source)), source);
+    }
+
+    #[test]
+    fn lexer_never_panics(source in any::<String>()) {
+        let _ = Lexer::new(&source).tokenize_all();
+    }
+
+    #[test]
+    fn lexer_never_panics_on_symbols(source in "[ (){}\\[\\],;+\\-*/=<>!&|\"'`\\\\.0-9a-zA-Z_]{0,40}") {
+        let _ = Lexer::new(&source).tokenize_all();
+    }
+
+    #[test]
+    fn parser_never_panics(source in "[ (){}\\[\\],;:.+\\-*/%=<>!&|^\"0-9a-z_]{0,40}") {
+        if let Ok(mut parser) = Parser::new(&source) {
+            let _ = parser.parse();
+        }
+    }
+}
@@ -1,10 +1,18 @@
-import React, { useState, useEffect } from 'react';
+import React, { useState, useEffect, useRef } from 'react';
+import axios from 'axios';
 import CodeEditor from './components/CodeEditor';
 import TokenDisplay from './components/TokenDisplay';
 import ASTVisualization from './components/ASTVisualization';
 import SemanticAnalysis from './components/SemanticAnalysis';
 import { compilerApi } from './services/api';
-import { TokenInfo, NodeData, EdgeData, Example, SemanticStep, SymbolInfo, TypeCheck } from './types';
+import { LiveAnalysisClient } from './services/live';
+import { ApiError, CompileResponse, TokenInfo, NodeData, EdgeData, Example, SemanticStep, SymbolInfo, TypeCheck } from './types';
+
+// Prefixes the location when the error points into the program
+const errorText = (error: ApiError | null): string | null => {
+  if (!error) return null;
+  return error.span ? `${error.span.line}:${error.span.column}: ${error.message}` : error.message;
+};
 
 function App() {
   const [code, setCode] = useState('x = 5 + 3 * 2');
@@ -15,6 +23,8 @@ function App() {
   const [symbolTable, setSymbolTable] = useState<SymbolInfo[]>([]);
   const [typeChecks, setTypeChecks] = useState<TypeCheck[]>([]);
   const [examples, setExamples] = useState<Example[]>([]);
+  const [categories, setCategories] = useState<string[]>([]);
+  const [category, setCategory] = useState('');
   const [loading, setLoading] = useState({
     tokens: false,
     ast: false,
@@ -28,26 +38,48 @@ function App() {
     examples: null as string | null
   });
 
-  // Load examples on component mount
+  const [shareStatus, setShareStatus] = useState<string | null>(null);
+
+  const liveClient = useRef<LiveAnalysisClient | null>(null);
+  const [liveConnected, setLiveConnected] = useState(false);
+
+  // Load examples and open the live-analysis socket on component mount
   useEffect(() => {
     loadExamples();
+    // Links made with Share open as /?s=<id>
+    const sharedId = new URLSearchParams(window.location.search).get('s');
+    if (sharedId) {
+      loadSharedSnippet(sharedId);
+    }
+    const client = new LiveAnalysisClient(showResults, setLiveConnected);
+    client.connect(code);
+    liveClient.current = client;
+    return () => client.close();
   }, []);
 
-  // Auto-compile when code changes
+  // Re-analyze when code changes: through the socket when connected, otherwise over HTTP
   useEffect(() => {
+    if (liveConnected) {
+      liveClient.current?.update(code);
+      return;
+    }
     if (code.trim()) {
       const timeoutId = setTimeout(() => {
         compileCode();
       }, 500); // Debounce
       return () => clearTimeout(timeoutId);
     }
-  }, [code]);
+  }, [code, liveConnected]);
 
-  const loadExamples = async () => {
+  const loadExamples = async (selected = '') => {
     setLoading(prev => ({ ...prev, examples: true }));
     try {
-      const response = await compilerApi.getExamples();
+      const response = await compilerApi.getExamples(selected || undefined);
       setExamples(response.examples);
+      // The unfiltered list is the one that knows every category
+      if (!selected) {
+        setCategories(Array.from(new Set(response.examples.map(example => example.category))));
+      }
       setError(prev => ({ ...prev, examples: null }));
     } catch (err) {
       setError(prev => ({ ...prev, examples: 'Failed to load examples' }));
@@ -56,56 +88,64 @@ function App() {
     }
   };
 
-  const compileCode = async () => {
-    // Tokenize
-    setLoading(prev => ({ ...prev, tokens: true }));
+  const loadSharedSnippet = async (id: string) => {
     try {
-      const tokensResponse = await compilerApi.tokenize(code);
-      if (tokensResponse.success) {
-        setTokens(tokensResponse.tokens);
-        setError(prev => ({ ...prev, tokens: null }));
-      } else {
-        setError(prev => ({ ...prev, tokens: tokensResponse.error }));
-      }
+      const snippet = await compilerApi.loadSnippet(id);
+      setCode(snippet.code);
     } catch (err) {
-      setError(prev => ({ ...prev, tokens: 'Failed to tokenize code' }));
-    } finally {
-      setLoading(prev => ({ ...prev, tokens: false }));
+      setShareStatus('This shared program does not exist or has expired');
     }
+  };
 
-    // Parse and visualize
-    setLoading(prev => ({ ...prev, ast: true }));
+  const shareCode = async () => {
     try {
-      const vizResponse = await compilerApi.visualize(code);
-      if (vizResponse.success) {
-        setAstNodes(vizResponse.nodes);
-        setAstEdges(vizResponse.edges);
-        setError(prev => ({ ...prev, ast: null }));
-      } else {
-        setError(prev => ({ ...prev, ast: vizResponse.error }));
-      }
+      const { id } = await compilerApi.saveSnippet(code);
+      const url = `${window.location.origin}${window.location.pathname}?s=${id}`;
+      window.history.replaceState(null, '', url);
+      await navigator.clipboard?.writeText(url);
+      setShareStatus(`Link copied: ${url}`);
     } catch (err) {
-      setError(prev => ({ ...prev, ast: 'Failed to parse code' }));
-    } finally {
-      setLoading(prev => ({ ...prev, ast: false }));
+      const message = axios.isAxiosError(err) && err.response?.data?.error
+        ? err.response.data.error.message
+        : 'Failed to share code';
+      setShareStatus(message);
+    }
+  };
+
+  // A failing stage leaves the later ones null
+  const showResults = (response: CompileResponse) => {
+    if (response.tokens) {
+      setTokens(response.tokens);
+    }
+    setError(prev => ({
+      ...prev,
+      tokens: response.error?.stage === 'tokens' ? errorText(response.error) : null,
+      ast: response.graph ? null : errorText(response.error),
+      semantic: response.semantics ? null : errorText(response.error),
+    }));
+    if (response.graph) {
+      setAstNodes(response.graph.nodes);
+      setAstEdges(response.graph.edges);
     }
+    if (response.semantics) {
+      setSemanticSteps(response.semantics.steps);
+      setSymbolTable(response.semantics.symbol_table);
+      setTypeChecks(response.semantics.type_checks);
+    }
+  };
 
-    // Semantic analysis
-    setLoading(prev => ({ ...prev, semantic: true }));
+  const compileCode = async () => {
+    setLoading(prev => ({ ...prev, tokens: true, ast: true, semantic: true }));
     try {
-      const semanticResponse = await compilerApi.semanticAnalysis(code);
-      if (semanticResponse.success) {
-        setSemanticSteps(semanticResponse.steps);
-        setSymbolTable(semanticResponse.symbol_table);
-        setTypeChecks(semanticResponse.type_checks);
-        setError(prev => ({ ...prev, semantic: null }));
-      } else {
-        setError(prev => ({ ...prev, semantic: semanticResponse.error }));
-      }
+      // One request runs the whole pipeline
+      showResults(await compilerApi.compile(code));
     } catch (err) {
-      setError(prev => ({ ...prev, semantic: 'Failed to perform semantic analysis' }));
+      const message = axios.isAxiosError(err) && err.response?.data?.error
+        ? errorText(err.response.data.error)
+        : 'Failed to compile code';
+      setError(prev => ({ ...prev, tokens: message, ast: message, semantic: message }));
     } finally {
-      setLoading(prev => ({ ...prev, semantic: false }));
+      setLoading(prev => ({ ...prev, tokens: false, ast: false, semantic: false }));
     }
   };
 
@@ -113,6 +153,11 @@ function App() {
     setCode(example.code);
   };
 
+  const handleCategoryChange = (selected: string) => {
+    setCategory(selected);
+    loadExamples(selected);
+  };
+
   return (
     <div className="min-h-screen bg-gray-100 p-4">
       <div className="max-w-7xl mx-auto">
@@ -128,7 +173,20 @@ function App() {
 
         {/* Examples */}
         <div className="bg-white rounded-lg shadow-sm p-6 mb-6">
-          <h2 className="text-xl font-semibold mb-4">📚 Examples</h2>
+          <div className="flex items-center justify-between mb-4">
+            <h2 className="text-xl font-semibold">📚 Examples</h2>
+            <select
+              value={category}
+              onChange={(e) => handleCategoryChange(e.target.value)}
+              className="border border-gray-300 rounded px-2 py-1 text-sm"
+              aria-label="Filter examples by category"
+            >
+              <option value="">All categories</option>
+              {categories.map(name => (
+                <option key={name} value={name}>{name}</option>
+              ))}
+            </select>
+          </div>
           <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
             {examples.map((example, index) => (
               <button
@@ -136,7 +194,10 @@ function App() {
                 onClick={() => handleExampleSelect(example)}
                 className="p-4 border border-gray-200 rounded-lg hover:bg-gray-50 text-left transition-colors"
               >
-                <div className="font-semibold text-gray-900 mb-1">{example.name}</div>
+                <div className="flex items-center justify-between mb-1">
+                  <span className="font-semibold text-gray-900">{example.name}</span>
+                  <span className="text-xs text-gray-500">{example.difficulty}</span>
+                </div>
                 <div className="text-sm text-gray-600 mb-2">{example.description}</div>
                 <div className="text-xs font-mono bg-gray-100 p-2 rounded text-gray-800">
                   {example.code}
@@ -167,7 +228,17 @@ function App() {
             >
               🗑️ Clear
             </button>
+            <button
+              onClick={shareCode}
+              disabled={!code.trim()}
+              className="px-4 py-2 bg-green-600 text-white rounded-lg hover:bg-green-700 transition-colors disabled:opacity-50"
+            >
+              🔗 Share
+            </button>
           </div>
+          {shareStatus && (
+            <p className="mt-2 text-sm text-gray-600 break-all" role="status">{shareStatus}</p>
+          )}
         </div>
 
         {/* Results */}
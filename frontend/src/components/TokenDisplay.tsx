@@ -1,5 +1,26 @@
 import React from 'react';
-import { TokenInfo } from '../types';
+import { TokenCategory, TokenInfo, Trivia } from '../types';
+
+// Comments are shown with the token they precede; whitespace is left out
+const commentText = (trivia: Trivia): string | null => {
+  if ('LineComment' in trivia) return trivia.LineComment;
+  if ('BlockComment' in trivia) return trivia.BlockComment;
+  return null;
+};
+
+const categoryClass: Partial<Record<TokenCategory, string>> = {
+  Keyword: 'font-semibold text-purple-700',
+  Number: 'font-semibold text-green-700',
+  String: 'font-semibold text-amber-700',
+  Rune: 'font-semibold text-amber-700',
+};
+
+// Extra details the lexer reports for some tokens
+const tokenDetail = (token: TokenInfo): string | null => {
+  if (token.numeric_kind !== null) return token.numeric_kind === 'Float' ? 'float' : 'int';
+  if (token.bracket_depth !== null) return `depth ${token.bracket_depth}`;
+  return null;
+};
 
 interface TokenDisplayProps {
   tokens: TokenInfo[];
@@ -51,28 +72,46 @@ const TokenDisplay: React.FC<TokenDisplayProps> = ({ tokens, loading, error }) =
       </h3>
       <div className="space-y-2 max-h-96 overflow-y-auto" role="list">
         {tokens.map((token, index) => (
-          <div
-            key={index} // Replace with unique ID if available: key={token.id}
-            className="flex items-center justify-between p-2 bg-gray-50 rounded"
-            role="listitem"
-          >
-            <div className="flex items-center space-x-3">
-              {/* Token position */}
-              <span className="text-sm font-mono bg-blue-100 text-blue-800 px-2 py-1 rounded">
-                {token.position}
-              </span>
-              {/* Token type */}
-              <span className="font-semibold text-gray-700">{token.token_type}</span>
-            </div>
-
-            {/* Raw token value (truncated if long) */}
+          <React.Fragment key={index}>
+            {token.leading_trivia
+              .map(commentText)
+              .filter((comment): comment is string => comment !== null)
+              .map((comment, commentIndex) => (
+                <div
+                  key={`comment-${commentIndex}`}
+                  className="px-2 text-sm font-mono italic text-gray-400 truncate"
+                  title={comment}
+                >
+                  {comment}
+                </div>
+              ))}
             <div
-              className="text-sm text-gray-600 font-mono max-w-xs truncate"
-              title={token.raw_value}
+              className="flex items-center justify-between p-2 bg-gray-50 rounded"
+              role="listitem"
             >
-              {token.raw_value}
+              <div className="flex items-center space-x-3">
+                {/* Token position */}
+                <span className="text-sm font-mono bg-blue-100 text-blue-800 px-2 py-1 rounded">
+                  {token.position}
+                </span>
+                {/* Token category */}
+                <span className={categoryClass[token.category] ?? 'font-semibold text-gray-700'}>
+                  {token.category}
+                </span>
+                {tokenDetail(token) && (
+                  <span className="text-xs text-gray-500">{tokenDetail(token)}</span>
+                )}
+              </div>
+
+              {/* Source text of the token (truncated if long) */}
+              <div
+                className="text-sm text-gray-600 font-mono max-w-xs truncate"
+                title={token.lexeme}
+              >
+                {token.lexeme}
+              </div>
             </div>
-          </div>
+          </React.Fragment>
         ))}
       </div>
     </section>
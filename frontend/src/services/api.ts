@@ -1,7 +1,7 @@
 import axios from 'axios';
-import { TokenizeResponse, ParseResponse, VisualizationResponse, ExampleResponse, SemanticAnalysisResponse } from '../types';
+import { CompileResponse, Stage, TokenizeResponse, ParseResponse, VisualizationResponse, ExampleResponse, SemanticAnalysisResponse, SaveSnippetResponse, Snippet } from '../types';
 
-const API_BASE_URL = 'http://localhost:3000';
+export const API_BASE_URL = 'http://localhost:3000';
 
 const apiClient = axios.create({
   baseURL: API_BASE_URL,
@@ -11,28 +11,46 @@ const apiClient = axios.create({
 });
 
 export const compilerApi = {
+  compile: async (code: string, stages?: Stage[]): Promise<CompileResponse> => {
+    // A program that does not compile comes back as 422 with the stages that ran
+    const response = await apiClient.post('/api/v1/compile', { code, include_trivia: true, stages }, {
+      validateStatus: (status) => status === 200 || status === 422,
+    });
+    return response.data;
+  },
+
   tokenize: async (code: string): Promise<TokenizeResponse> => {
-    const response = await apiClient.post('/api/tokenize', { code });
+    const response = await apiClient.post('/api/v1/tokenize', { code, include_trivia: true });
     return response.data;
   },
 
   parse: async (code: string): Promise<ParseResponse> => {
-    const response = await apiClient.post('/api/parse', { code });
+    const response = await apiClient.post('/api/v1/parse', { code });
     return response.data;
   },
 
   visualize: async (code: string): Promise<VisualizationResponse> => {
-    const response = await apiClient.post('/api/visualize', { code });
+    const response = await apiClient.post('/api/v1/visualize', { code });
     return response.data;
   },
 
-  getExamples: async (): Promise<ExampleResponse> => {
-    const response = await apiClient.get('/api/examples');
+  getExamples: async (category?: string): Promise<ExampleResponse> => {
+    const response = await apiClient.get('/api/v1/examples', { params: { category } });
     return response.data;
   },
 
   semanticAnalysis: async (code: string): Promise<SemanticAnalysisResponse> => {
-    const response = await apiClient.post('/api/semantic-analysis', { code });
+    const response = await apiClient.post('/api/v1/semantic-analysis', { code });
+    return response.data;
+  },
+
+  saveSnippet: async (code: string): Promise<SaveSnippetResponse> => {
+    const response = await apiClient.post('/api/v1/snippets', { code });
+    return response.data;
+  },
+
+  loadSnippet: async (id: string): Promise<Snippet> => {
+    const response = await apiClient.get(`/api/v1/snippets/${encodeURIComponent(id)}`);
     return response.data;
   },
 
@@ -0,0 +1,84 @@
+import { API_BASE_URL } from './api';
+import { LiveAnalysis, Stage } from '../types';
+
+const LIVE_URL = `${API_BASE_URL.replace(/^http/, 'ws')}/api/v1/live`;
+
+// Smallest single edit turning `before` into `after`, in UTF-16 offsets like the server expects
+const diff = (before: string, after: string) => {
+  let start = 0;
+  while (start < before.length && start < after.length && before[start] === after[start]) {
+    start++;
+  }
+  let end = 0;
+  while (
+    end < before.length - start &&
+    end < after.length - start &&
+    before[before.length - 1 - end] === after[after.length - 1 - end]
+  ) {
+    end++;
+  }
+  return { start, end: before.length - end, text: after.slice(start, after.length - end) };
+};
+
+/**
+ * Keeps a document in sync with the server's live-analysis socket.
+ * The server debounces edits and only answers for the latest version.
+ */
+export class LiveAnalysisClient {
+  private socket: WebSocket | null = null;
+  private sent = '';
+  private version = 0;
+
+  constructor(
+    private onAnalysis: (analysis: LiveAnalysis) => void,
+    private onStatus: (connected: boolean) => void,
+    private stages?: Stage[],
+  ) {}
+
+  connect(code: string) {
+    this.socket = new WebSocket(LIVE_URL);
+    this.socket.onopen = () => {
+      this.onStatus(true);
+      this.open(code);
+    };
+    this.socket.onclose = () => this.onStatus(false);
+    this.socket.onmessage = (event) => {
+      const message = JSON.parse(event.data);
+      if (message.type === 'analysis') {
+        this.onAnalysis(message);
+      } else if (message.type === 'error') {
+        // Our copy of the document may have drifted; send it whole again
+        this.open(this.sent);
+      }
+    };
+  }
+
+  update(code: string) {
+    if (this.socket?.readyState !== WebSocket.OPEN || code === this.sent) return;
+    this.version++;
+    this.socket.send(JSON.stringify({
+      type: 'edit',
+      version: this.version,
+      changes: [diff(this.sent, code)],
+    }));
+    this.sent = code;
+  }
+
+  close() {
+    this.socket?.close();
+    this.socket = null;
+  }
+
+  private open(code: string) {
+    if (this.socket?.readyState !== WebSocket.OPEN) return;
+    this.version++;
+    this.socket.send(JSON.stringify({
+      type: 'open',
+      version: this.version,
+      code,
+      include_trivia: true,
+      stages: this.stages,
+    }));
+    this.sent = code;
+  }
+}
@@ -1,13 +1,33 @@
+export type Trivia =
+  | { Whitespace: string }
+  | { LineComment: string }
+  | { BlockComment: string };
+
+export type TokenCategory =
+  | 'Punctuation'
+  | 'Operator'
+  | 'Identifier'
+  | 'Keyword'
+  | 'Rune'
+  | 'Number'
+  | 'String'
+  | 'Boolean'
+  | 'Unknown'
+  | 'EOF';
+
 export interface TokenInfo {
-  token_type: string;
-  raw_value: string;
+  category: TokenCategory;
+  lexeme: string;
   position: number;
+  line: number;
+  column: number;
+  numeric_kind: 'Integer' | 'Float' | null;
+  bracket_depth: number | null;
+  leading_trivia: Trivia[];
 }
 
 export interface TokenizeResponse {
   tokens: TokenInfo[];
-  success: boolean;
-  error: string | null;
 }
 
 export interface ASTNode {
@@ -16,9 +36,7 @@ export interface ASTNode {
 }
 
 export interface ParseResponse {
-  ast: ASTNode | null;
-  success: boolean;
-  error: string | null;
+  ast: ASTNode;
 }
 
 export interface NodeData {
@@ -37,15 +55,16 @@ export interface VisualizationResponse {
   dot_content: string;
   nodes: NodeData[];
   edges: EdgeData[];
-  success: boolean;
-  error: string | null;
 }
 
+export type Difficulty = 'beginner' | 'intermediate' | 'advanced';
+
 export interface Example {
   name: string;
   code: string;
   description: string;
   category: string;
+  difficulty: Difficulty;
 }
 
 export interface SemanticStep {
@@ -78,8 +97,61 @@ export interface SemanticAnalysisResponse {
   steps: SemanticStep[];
   symbol_table: SymbolInfo[];
   type_checks: TypeCheck[];
-  success: boolean;
-  error: string | null;
+}
+
+export type Stage = 'tokens' | 'ast' | 'graph' | 'semantics';
+
+// Body of every error response, and of live diagnostics
+export interface ApiError {
+  code: string;
+  message: string;
+  span: { line: number; column: number } | null;
+  stage: Stage | null;
+}
+
+export interface ErrorEnvelope {
+  error: ApiError;
+}
+
+export interface StageTiming {
+  stage: Stage;
+  duration_ms: number;
+}
+
+export interface CompileResponse {
+  tokens: TokenInfo[] | null;
+  ast: ASTNode | null;
+  graph: {
+    dot_content: string;
+    nodes: NodeData[];
+    edges: EdgeData[];
+  } | null;
+  semantics: {
+    steps: SemanticStep[];
+    symbol_table: SymbolInfo[];
+    type_checks: TypeCheck[];
+  } | null;
+  timings: StageTiming[];
+  total_ms: number;
+  error: ApiError | null;
+}
+
+export interface LiveAnalysis extends CompileResponse {
+  type: 'analysis';
+  version: number;
+  diagnostics: ApiError[];
+}
+
+export interface Snippet {
+  id: string;
+  code: string;
+  created_at: number;
+  expires_at: number | null;
+}
+
+export interface SaveSnippetResponse {
+  id: string;
+  expires_at: number | null;
 }
 
 export interface ExampleResponse {
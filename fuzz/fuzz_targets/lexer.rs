@@ -0,0 +1,8 @@
+#![no_main]
+
+use compiler_core::lexer::Lexer;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source: &str| {
+    let _ = Lexer::new(source).tokenize_all();
+});
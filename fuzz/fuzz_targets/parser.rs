@@ -0,0 +1,10 @@
+#![no_main]
+
+use compiler_core::parser::Parser;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source: &str| {
+    if let Ok(mut parser) = Parser::new(source) {
+        let _ = parser.parse();
+    }
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use compiler_core::graphviz::GraphvizRenderer;
+use compiler_core::parser::Parser;
+use compiler_core::printer::print_ast;
+use compiler_core::semantic::analyze;
+use libfuzzer_sys::fuzz_target;
+
+// Runs every stage the server runs on a request body
+fuzz_target!(|source: &str| {
+    let Ok(ast) = Parser::new(source).and_then(|mut parser| parser.parse()) else {
+        return;
+    };
+    let _ = analyze(&ast);
+    let _ = GraphvizRenderer::new().render_to_dot(&ast);
+    let _ = print_ast(&ast);
+});
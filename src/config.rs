@@ -0,0 +1,439 @@
+//! Server configuration.
+//!
+//! Settings come from built-in defaults, then an optional TOML file, then
+//! `COMPILER_*` environment variables, each overriding the previous one. The
+//! file is read from `COMPILER_CONFIG` when set, or from `compiler.toml` in the
+//! working directory if it exists:
+//!
+//! ```toml
+//! log_level = "info"
+//! log_format = "text"
+//!
+//! [server]
+//! host = "0.0.0.0"
+//! port = 3000
+//! cors_origins = ["http://localhost:3001"]
+//! body_limit_bytes = 1048576
+//! shutdown_timeout_secs = 10
+//!
+//! [analysis]
+//! max_source_bytes = 65536
+//! live_debounce_ms = 150
+//!
+//! [snippets]
+//! backend = "filesystem"
+//! path = "snippets"
+//! max_bytes = 65536
+//! expiry_days = 30
+//! ```
+
+use serde::Deserialize;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const DEFAULT_CONFIG_FILE: &str = "compiler.toml";
+const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+/// Longest snippet expiry that can be configured, about a century
+const MAX_EXPIRY_DAYS: u64 = 36500;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("cannot read config file {path}: {source}")]
+    Read { path: PathBuf, source: std::io::Error },
+    
+    #[error("invalid config file {path}: {source}")]
+    Parse { path: PathBuf, source: toml::de::Error },
+    
+    #[error("invalid value {value:?} for {var}: {reason}")]
+    Env { var: String, value: String, reason: String },
+    
+    #[error("invalid setting {setting}: {reason}")]
+    Invalid { setting: &'static str, reason: String },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub log_level: String,
+    pub log_format: LogFormat,
+    pub server: ServerConfig,
+    pub analysis: AnalysisConfig,
+    pub snippets: SnippetsConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per event, for log collectors
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+    
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err("expected \"text\" or \"json\"".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// Origins allowed by CORS; `"*"` allows any origin
+    pub cors_origins: Vec<String>,
+    /// Largest request body accepted, in bytes
+    pub body_limit_bytes: usize,
+    /// How long in-flight requests may take to finish once shutdown starts
+    pub shutdown_timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnalysisConfig {
+    /// Largest program the compiler endpoints will analyze, in bytes
+    pub max_source_bytes: usize,
+    /// Quiet period after the last live edit before the pipeline reruns
+    pub live_debounce_ms: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnippetsConfig {
+    pub backend: SnippetBackend,
+    /// Directory of the filesystem backend, or database file of the SQLite one
+    pub path: PathBuf,
+    /// Largest program that can be shared, in bytes
+    pub max_bytes: usize,
+    /// Days a shared program is kept after it was last saved; 0 keeps it forever
+    pub expiry_days: u64,
+}
+
+/// Where shared programs are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnippetBackend {
+    /// One JSON file per program in a directory
+    Filesystem,
+    /// A single SQLite database file
+    Sqlite,
+}
+
+impl std::str::FromStr for SnippetBackend {
+    type Err = String;
+    
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "filesystem" => Ok(SnippetBackend::Filesystem),
+            "sqlite" => Ok(SnippetBackend::Sqlite),
+            _ => Err("expected \"filesystem\" or \"sqlite\"".to_string()),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            log_level: "info".to_string(),
+            log_format: LogFormat::Text,
+            server: ServerConfig::default(),
+            analysis: AnalysisConfig::default(),
+            snippets: SnippetsConfig::default(),
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            host: "0.0.0.0".to_string(),
+            port: 3000,
+            cors_origins: vec!["*".to_string()],
+            body_limit_bytes: 1024 * 1024,
+            shutdown_timeout_secs: 10,
+        }
+    }
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        AnalysisConfig {
+            max_source_bytes: 64 * 1024,
+            live_debounce_ms: 150,
+        }
+    }
+}
+
+impl Default for SnippetsConfig {
+    fn default() -> Self {
+        SnippetsConfig {
+            backend: SnippetBackend::Filesystem,
+            path: PathBuf::from("snippets"),
+            max_bytes: 64 * 1024,
+            expiry_days: 30,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the configuration from the config file and the process environment
+    pub fn load() -> Result<Config, ConfigError> {
+        let path = match std::env::var_os("COMPILER_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|path| path.exists()),
+        };
+        let file = match path {
+            Some(path) => {
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|source| ConfigError::Read { path: path.clone(), source })?;
+                Some((path, contents))
+            },
+            None => None,
+        };
+        let file = file.as_ref().map(|(path, contents)| (path.as_path(), contents.as_str()));
+        Config::from_sources(file, |var| std::env::var(var).ok())
+    }
+    
+    /// Builds the configuration from an optional `(path, contents)` TOML file
+    /// and an environment lookup, then validates it
+    pub fn from_sources(
+        file: Option<(&Path, &str)>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Config, ConfigError> {
+        let mut config = match file {
+            Some((path, contents)) => toml::from_str(contents)
+                .map_err(|source| ConfigError::Parse { path: path.to_path_buf(), source })?,
+            None => Config::default(),
+        };
+        
+        if let Some(value) = env("COMPILER_LOG_LEVEL") {
+            config.log_level = value;
+        }
+        if let Some(value) = env("COMPILER_LOG_FORMAT") {
+            config.log_format = parse_env("COMPILER_LOG_FORMAT", value)?;
+        }
+        if let Some(value) = env("COMPILER_HOST") {
+            config.server.host = value;
+        }
+        if let Some(value) = env("COMPILER_PORT") {
+            config.server.port = parse_env("COMPILER_PORT", value)?;
+        }
+        if let Some(value) = env("COMPILER_CORS_ORIGINS") {
+            config.server.cors_origins = value.split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect();
+        }
+        if let Some(value) = env("COMPILER_BODY_LIMIT_BYTES") {
+            config.server.body_limit_bytes = parse_env("COMPILER_BODY_LIMIT_BYTES", value)?;
+        }
+        if let Some(value) = env("COMPILER_SHUTDOWN_TIMEOUT_SECS") {
+            config.server.shutdown_timeout_secs = parse_env("COMPILER_SHUTDOWN_TIMEOUT_SECS", value)?;
+        }
+        if let Some(value) = env("COMPILER_MAX_SOURCE_BYTES") {
+            config.analysis.max_source_bytes = parse_env("COMPILER_MAX_SOURCE_BYTES", value)?;
+        }
+        if let Some(value) = env("COMPILER_LIVE_DEBOUNCE_MS") {
+            config.analysis.live_debounce_ms = parse_env("COMPILER_LIVE_DEBOUNCE_MS", value)?;
+        }
+        if let Some(value) = env("COMPILER_SNIPPETS_BACKEND") {
+            config.snippets.backend = parse_env("COMPILER_SNIPPETS_BACKEND", value)?;
+        }
+        if let Some(value) = env("COMPILER_SNIPPETS_PATH") {
+            config.snippets.path = PathBuf::from(value);
+        }
+        if let Some(value) = env("COMPILER_SNIPPETS_MAX_BYTES") {
+            config.snippets.max_bytes = parse_env("COMPILER_SNIPPETS_MAX_BYTES", value)?;
+        }
+        if let Some(value) = env("COMPILER_SNIPPETS_EXPIRY_DAYS") {
+            config.snippets.expiry_days = parse_env("COMPILER_SNIPPETS_EXPIRY_DAYS", value)?;
+        }
+        
+        config.validate()?;
+        Ok(config)
+    }
+    
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !LOG_LEVELS.contains(&self.log_level.as_str()) {
+            return Err(ConfigError::Invalid {
+                setting: "log_level",
+                reason: format!("{:?} is not one of {}", self.log_level, LOG_LEVELS.join(", ")),
+            });
+        }
+        self.bind_address()?;
+        if self.server.cors_origins.is_empty() {
+            return Err(ConfigError::Invalid {
+                setting: "server.cors_origins",
+                reason: "at least one origin (or \"*\") is required".to_string(),
+            });
+        }
+        for origin in &self.server.cors_origins {
+            let valid = origin == "*"
+                || ((origin.starts_with("http://") || origin.starts_with("https://"))
+                    && !origin.ends_with('/')
+                    && origin.parse::<axum::http::HeaderValue>().is_ok());
+            if !valid {
+                return Err(ConfigError::Invalid {
+                    setting: "server.cors_origins",
+                    reason: format!("{:?} is not \"*\" or an http(s) origin like http://localhost:3001", origin),
+                });
+            }
+        }
+        if self.server.body_limit_bytes == 0 {
+            return Err(ConfigError::Invalid {
+                setting: "server.body_limit_bytes",
+                reason: "must be greater than zero".to_string(),
+            });
+        }
+        if self.analysis.max_source_bytes == 0 || self.analysis.max_source_bytes > self.server.body_limit_bytes {
+            return Err(ConfigError::Invalid {
+                setting: "analysis.max_source_bytes",
+                reason: format!(
+                    "must be between 1 and server.body_limit_bytes ({})",
+                    self.server.body_limit_bytes
+                ),
+            });
+        }
+        // A shared program must still be small enough to analyze
+        if self.snippets.max_bytes == 0 || self.snippets.max_bytes > self.analysis.max_source_bytes {
+            return Err(ConfigError::Invalid {
+                setting: "snippets.max_bytes",
+                reason: format!(
+                    "must be between 1 and analysis.max_source_bytes ({})",
+                    self.analysis.max_source_bytes
+                ),
+            });
+        }
+        if self.snippets.expiry_days > MAX_EXPIRY_DAYS {
+            return Err(ConfigError::Invalid {
+                setting: "snippets.expiry_days",
+                reason: format!("must be at most {} (0 keeps programs forever)", MAX_EXPIRY_DAYS),
+            });
+        }
+        if self.snippets.path.as_os_str().is_empty() {
+            return Err(ConfigError::Invalid {
+                setting: "snippets.path",
+                reason: "must not be empty".to_string(),
+            });
+        }
+        Ok(())
+    }
+    
+    /// Resolves the configured host and port to the address to listen on
+    pub fn bind_address(&self) -> Result<SocketAddr, ConfigError> {
+        (self.server.host.as_str(), self.server.port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| ConfigError::Invalid {
+                setting: "server.host",
+                reason: format!("cannot resolve {:?}", self.server.host),
+            })
+    }
+    
+    pub fn allows_any_origin(&self) -> bool {
+        self.server.cors_origins.iter().any(|origin| origin == "*")
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(var: &str, value: String) -> Result<T, ConfigError>
+where
+    T::Err: std::fmt::Display,
+{
+    value.trim().parse().map_err(|e: T::Err| ConfigError::Env {
+        var: var.to_string(),
+        reason: e.to_string(),
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    
+    fn load(file: Option<&str>, env: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let env: HashMap<String, String> = env.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Config::from_sources(
+            file.map(|contents| (Path::new("compiler.toml"), contents)),
+            |var| env.get(var).cloned(),
+        )
+    }
+    
+    #[test]
+    fn test_defaults_file_and_env_layering() {
+        let config = load(None, &[]).unwrap();
+        assert_eq!(config.bind_address().unwrap().port(), 3000);
+        assert!(config.allows_any_origin());
+        
+        let file = r#"
+            log_level = "debug"
+            [server]
+            port = 8080
+            cors_origins = ["http://localhost:3001"]
+            [analysis]
+            max_source_bytes = 1000
+            [snippets]
+            backend = "sqlite"
+            path = "data/snippets.db"
+            max_bytes = 500
+        "#;
+        let config = load(Some(file), &[]).unwrap();
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(config.server.host, "0.0.0.0");
+        assert!(!config.allows_any_origin());
+        assert_eq!(config.analysis.max_source_bytes, 1000);
+        assert_eq!(config.analysis.live_debounce_ms, 150);
+        assert_eq!(config.snippets.backend, SnippetBackend::Sqlite);
+        assert_eq!(config.snippets.expiry_days, 30);
+        
+        // Environment variables win over the file
+        let config = load(Some(file), &[
+            ("COMPILER_PORT", "9000"),
+            ("COMPILER_LOG_FORMAT", "json"),
+            ("COMPILER_CORS_ORIGINS", "https://a.example, https://b.example"),
+            ("COMPILER_SNIPPETS_BACKEND", "filesystem"),
+            ("COMPILER_SNIPPETS_EXPIRY_DAYS", "0"),
+        ]).unwrap();
+        assert_eq!(config.server.port, 9000);
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.server.cors_origins, vec!["https://a.example", "https://b.example"]);
+        assert_eq!(config.snippets.backend, SnippetBackend::Filesystem);
+        assert_eq!(config.snippets.expiry_days, 0);
+    }
+    
+    #[test]
+    fn test_invalid_configuration_is_rejected() {
+        assert!(matches!(load(None, &[("COMPILER_PORT", "http")]), Err(ConfigError::Env { .. })));
+        assert!(matches!(load(None, &[("COMPILER_LOG_FORMAT", "xml")]), Err(ConfigError::Env { .. })));
+        assert!(matches!(load(None, &[("COMPILER_SNIPPETS_BACKEND", "redis")]), Err(ConfigError::Env { .. })));
+        assert!(matches!(load(Some("[server]\nprot = 1"), &[]), Err(ConfigError::Parse { .. })));
+        assert!(matches!(load(Some("[server]\nport = \"80\""), &[]), Err(ConfigError::Parse { .. })));
+        
+        for env in [
+            ("COMPILER_LOG_LEVEL", "loud"),
+            ("COMPILER_CORS_ORIGINS", "localhost:3001"),
+            ("COMPILER_CORS_ORIGINS", ","),
+            ("COMPILER_BODY_LIMIT_BYTES", "0"),
+            ("COMPILER_MAX_SOURCE_BYTES", "2000000"),
+            ("COMPILER_SNIPPETS_MAX_BYTES", "100000"),
+            ("COMPILER_SNIPPETS_PATH", ""),
+            ("COMPILER_SNIPPETS_EXPIRY_DAYS", "36501"),
+            ("COMPILER_SNIPPETS_EXPIRY_DAYS", "18446744073709551615"),
+        ] {
+            let error = load(None, &[env]).unwrap_err();
+            assert!(matches!(error, ConfigError::Invalid { .. }), "{:?} gave {}", env, error);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Error responses.
+//!
+//! Every failure, whether the program does not compile or the request itself
+//! is malformed, is reported with the same envelope and a matching status:
+//!
+//! ```json
+//! {"error": {"code": "unexpected_token", "message": "...", "span": {"line": 1, "column": 5}, "stage": "ast"}}
+//! ```
+//!
+//! - 400: the body is not valid JSON, or a query parameter is invalid
+//! - 413: the body or the program is over the configured limit
+//! - 415: the request is missing `Content-Type: application/json`
+//! - 422: the JSON does not match the request schema, or the program fails to compile
+//! - 500: the server failed while handling the request
+
+use crate::{telemetry, Stage};
+use axum::{
+    extract::{rejection::{JsonRejection, QueryRejection}, FromRequest, FromRequestParts},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Where in the program an error was found; both start at 1
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+pub struct SourceSpan {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ErrorBody {
+    /// Machine-readable error code, e.g. `unexpected_token` or `invalid_json`
+    pub code: String,
+    /// Human-readable description
+    pub message: String,
+    /// Location in the program, for errors that have one
+    pub span: Option<SourceSpan>,
+    /// Compiler stage that failed, for errors in the program
+    pub stage: Option<Stage>,
+}
+
+impl ErrorBody {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        ErrorBody {
+            code: code.to_string(),
+            message: message.into(),
+            span: None,
+            stage: None,
+        }
+    }
+    
+    /// An error in the program found by `stage` at `line`, `column`
+    pub fn compile(stage: Stage, code: &str, message: impl Into<String>, (line, column): (usize, usize)) -> Self {
+        ErrorBody {
+            span: Some(SourceSpan { line, column }),
+            stage: Some(stage),
+            ..ErrorBody::new(code, message)
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorEnvelope {
+    pub error: ErrorBody,
+}
+
+/// An error response: the envelope plus its HTTP status
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    body: ErrorBody,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, body: ErrorBody) -> Self {
+        ApiError { status, body }
+    }
+    
+    /// The program does not compile
+    pub fn compile(body: ErrorBody) -> Self {
+        ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, body)
+    }
+    
+    pub fn source_too_large(size: usize, limit: usize) -> Self {
+        telemetry::record_error("source_too_large");
+        ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorBody::new("source_too_large", format!("program is {} bytes, the limit is {}", size, limit)),
+        )
+    }
+    
+    pub fn not_found() -> Self {
+        ApiError::new(StatusCode::NOT_FOUND, ErrorBody::new("not_found", "no such endpoint"))
+    }
+    
+    pub fn internal() -> Self {
+        telemetry::record_error("internal");
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorBody::new("internal_error", "the server failed while handling the request"),
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(ErrorEnvelope { error: self.body })).into_response()
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        let status = rejection.status();
+        let code = match &rejection {
+            JsonRejection::JsonSyntaxError(_) => "invalid_json",
+            JsonRejection::JsonDataError(_) => "invalid_request",
+            JsonRejection::MissingJsonContentType(_) => "unsupported_media_type",
+            _ if status == StatusCode::PAYLOAD_TOO_LARGE => "body_too_large",
+            _ => "invalid_body",
+        };
+        telemetry::record_error(code);
+        ApiError::new(status, ErrorBody::new(code, rejection.body_text()))
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        telemetry::record_error("invalid_query");
+        ApiError::new(rejection.status(), ErrorBody::new("invalid_query", rejection.body_text()))
+    }
+}
+
+/// JSON request body whose rejections use the error envelope
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct ApiJson<T>(pub T);
+
+/// Query string whose rejections use the error envelope
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
+
+/// Response for requests that matched no route
+pub async fn not_found() -> ApiError {
+    ApiError::not_found()
+}
+
+/// Turns a panic in a handler into a 500 response instead of a dropped connection
+pub fn panic_response(_panic: Box<dyn std::any::Any + Send + 'static>) -> Response {
+    tracing::error!("handler panicked");
+    ApiError::internal().into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    
+    async fn extract(content_type: Option<&str>, body: &'static str) -> Result<ApiJson<crate::CompileRequest>, ApiError> {
+        let mut request = Request::post("/api/v1/parse");
+        if let Some(content_type) = content_type {
+            request = request.header("content-type", content_type);
+        }
+        ApiJson::from_request(request.body(Body::from(body)).unwrap(), &()).await
+    }
+    
+    #[tokio::test]
+    async fn test_json_rejections_use_envelope_codes() {
+        let cases = [
+            (Some("application/json"), "{\"code\": ", StatusCode::BAD_REQUEST, "invalid_json"),
+            (Some("application/json"), "{\"source\": \"x\"}", StatusCode::UNPROCESSABLE_ENTITY, "invalid_request"),
+            (None, "{\"code\": \"x\"}", StatusCode::UNSUPPORTED_MEDIA_TYPE, "unsupported_media_type"),
+        ];
+        for (content_type, body, status, code) in cases {
+            let error = extract(content_type, body).await.err().expect("request is rejected");
+            assert_eq!(error.status, status);
+            assert_eq!(error.body.code, code);
+        }
+        assert!(extract(Some("application/json"), "{\"code\": \"x = 1\"}").await.is_ok());
+    }
+}
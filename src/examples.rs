@@ -0,0 +1,197 @@
+//! Example programs.
+//!
+//! Each example is a `.src` file under `examples/`, embedded at build time. The
+//! program is preceded by TOML front-matter between `+++` lines:
+//!
+//! ```text
+//! +++
+//! name = "Maps"
+//! description = "Map literals, indexing and delete"
+//! category = "collections"
+//! difficulty = "advanced"
+//!
+//! [expect]
+//! symbols = { ages = "map[string]int" }
+//! +++
+//! ages = map[string]int{"ana": 31}; delete(ages, "ana")
+//! ```
+//!
+//! `[expect]` describes what the compiler should make of the program, and the
+//! tests below check every example against it, so the files double as
+//! regression tests.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+const FRONT_MATTER_DELIMITER: &str = "+++";
+
+#[derive(Error, Debug)]
+pub enum ExampleError {
+    #[error("example {file} does not start with `+++` front-matter")]
+    MissingFrontMatter { file: String },
+    
+    #[error("invalid front-matter in example {file}: {source}")]
+    FrontMatter { file: String, source: toml::de::Error },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Example {
+    pub name: String,
+    pub code: String,
+    pub description: String,
+    pub category: String,
+    pub difficulty: Difficulty,
+}
+
+/// What the compiler should report for an example; the server only validates
+/// it, the tests below check it
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(not(test), allow(dead_code))]
+#[serde(deny_unknown_fields)]
+pub struct Expectation {
+    /// Code of the error that stops the pipeline; `None` when every stage succeeds
+    pub error: Option<String>,
+    /// Messages of the semantic errors, in order
+    #[serde(default)]
+    pub semantic_errors: Vec<String>,
+    /// Symbols that must be in the symbol table, with their types
+    #[serde(default)]
+    pub symbols: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FrontMatter {
+    name: String,
+    description: String,
+    category: String,
+    difficulty: Difficulty,
+    #[serde(default)]
+    expect: Expectation,
+}
+
+/// An example with the expectation from its front-matter
+#[derive(Debug)]
+#[cfg_attr(not(test), allow(dead_code))]
+pub struct ExampleFile {
+    pub file: String,
+    pub example: Example,
+    pub expect: Expectation,
+}
+
+/// Query parameters of `/api/v1/examples`; each one narrows the list
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExampleFilter {
+    /// Only examples in this category
+    pub category: Option<String>,
+    /// Only examples of this difficulty
+    pub difficulty: Option<Difficulty>,
+}
+
+impl ExampleFilter {
+    pub fn matches(&self, example: &Example) -> bool {
+        self.category.iter().all(|category| &example.category == category)
+            && self.difficulty.iter().all(|difficulty| example.difficulty == *difficulty)
+    }
+}
+
+/// Splits an example file into its front-matter and program
+pub fn parse(file: &str, contents: &str) -> Result<ExampleFile, ExampleError> {
+    let missing = || ExampleError::MissingFrontMatter { file: file.to_string() };
+    let rest = contents
+        .strip_prefix(FRONT_MATTER_DELIMITER)
+        .and_then(|rest| rest.strip_prefix('\n'))
+        .ok_or_else(missing)?;
+    let closing = format!("\n{}\n", FRONT_MATTER_DELIMITER);
+    let (header, code) = rest.split_once(&closing).ok_or_else(missing)?;
+    
+    let front_matter: FrontMatter = toml::from_str(header)
+        .map_err(|source| ExampleError::FrontMatter { file: file.to_string(), source })?;
+    Ok(ExampleFile {
+        file: file.to_string(),
+        example: Example {
+            name: front_matter.name,
+            code: code.trim_end().to_string(),
+            description: front_matter.description,
+            category: front_matter.category,
+            difficulty: front_matter.difficulty,
+        },
+        expect: front_matter.expect,
+    })
+}
+
+/// Parses every embedded example, in file name order
+pub fn load() -> Result<Vec<ExampleFile>, ExampleError> {
+    EXAMPLE_FILES.iter().map(|(file, contents)| parse(file, contents)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{all_stages, live, run_pipeline};
+    
+    #[test]
+    fn test_examples_match_their_expectations() {
+        let examples = load().expect("every example has valid front-matter");
+        assert!(!examples.is_empty());
+        
+        for ExampleFile { file, example, expect } in examples {
+            let result = run_pipeline(&example.code, false, &all_stages());
+            let error = result.error.as_ref().map(|error| error.code.as_str());
+            assert_eq!(error, expect.error.as_deref(), "{}: pipeline error", file);
+            
+            let semantic_errors: Vec<String> = live::diagnostics(&result)
+                .into_iter()
+                .filter(|diagnostic| diagnostic.code == "semantic_error")
+                .map(|diagnostic| diagnostic.message)
+                .collect();
+            assert_eq!(semantic_errors, expect.semantic_errors, "{}: semantic errors", file);
+            
+            let symbol_table = result.semantics.map(|semantics| semantics.symbol_table).unwrap_or_default();
+            for (name, data_type) in &expect.symbols {
+                let symbol = symbol_table.iter().find(|symbol| &symbol.name == name);
+                assert_eq!(symbol.map(|symbol| symbol.data_type.as_str()), Some(data_type.as_str()), "{}: type of {}", file, name);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_front_matter_is_required_and_validated() {
+        assert!(matches!(parse("bare.src", "x = 1\n"), Err(ExampleError::MissingFrontMatter { .. })));
+        assert!(matches!(
+            parse("typo.src", "+++\nname = \"x\"\ndescription = \"\"\ncategory = \"basic\"\ndifficulty = \"easy\"\n+++\nx = 1\n"),
+            Err(ExampleError::FrontMatter { .. })
+        ));
+        
+        let example = parse("ok.src", "+++\nname = \"x\"\ndescription = \"\"\ncategory = \"basic\"\ndifficulty = \"beginner\"\n+++\nx = 1\n")
+            .expect("valid example");
+        assert_eq!(example.example.code, "x = 1");
+        assert!(example.expect.error.is_none());
+    }
+    
+    #[test]
+    fn test_filter_by_category_and_difficulty() {
+        let examples: Vec<Example> = load().unwrap().into_iter().map(|file| file.example).collect();
+        let filter = ExampleFilter { category: Some("collections".to_string()), difficulty: None };
+        let collections: Vec<_> = examples.iter().filter(|example| filter.matches(example)).collect();
+        assert!(!collections.is_empty());
+        assert!(collections.iter().all(|example| example.category == "collections"));
+        
+        let filter = ExampleFilter { category: Some("collections".to_string()), difficulty: Some(Difficulty::Advanced) };
+        assert!(examples.iter().filter(|example| filter.matches(example)).all(|example| example.difficulty == Difficulty::Advanced));
+        assert_eq!(examples.iter().filter(|example| ExampleFilter::default().matches(example)).count(), examples.len());
+    }
+}
@@ -0,0 +1,104 @@
+//! Liveness and readiness probes.
+//!
+//! `/healthz` only says the process is up. `/readyz` also compiles a known
+//! program through every stage and reports 503 while that fails or while the
+//! server drains connections during shutdown.
+
+use crate::{all_stages, run_blocking, run_pipeline, AppState};
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+use std::sync::atomic::Ordering;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const GIT_COMMIT: &str = env!("GIT_COMMIT");
+
+/// Exercises the lexer, parser, graph rendering and semantic analysis
+const SELF_TEST_PROGRAM: &str = "type Point struct { X int; Y int }; p = Point{X: 1, Y: 2}; \
+    xs = []int{p.X, p.Y}; total = xs[0] + len(xs) * 2";
+
+#[derive(Serialize, ToSchema)]
+pub struct HealthResponse {
+    status: &'static str,
+    version: &'static str,
+    git_commit: &'static str,
+    uptime_seconds: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SelfTest {
+    passed: bool,
+    duration_ms: f64,
+    error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    status: &'static str,
+    version: &'static str,
+    git_commit: &'static str,
+    uptime_seconds: u64,
+    draining: bool,
+    self_test: SelfTest,
+}
+
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "health",
+    responses((status = 200, description = "The process is up", body = HealthResponse))
+)]
+pub async fn healthz(State(state): State<AppState>) -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok",
+        version: VERSION,
+        git_commit: GIT_COMMIT,
+        uptime_seconds: state.started_at.elapsed().as_secs(),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "health",
+    responses(
+        (status = 200, description = "Ready to serve requests", body = ReadinessResponse),
+        (status = 503, description = "Self-test failed or shutting down", body = ReadinessResponse),
+    )
+)]
+pub async fn readyz(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    // Compiles like a request does, so frequent probes do not stall the async workers
+    let self_test = run_blocking(self_test).await.unwrap_or_else(|_| SelfTest {
+        passed: false,
+        duration_ms: 0.0,
+        error: Some("self-test did not finish".to_string()),
+    });
+    let draining = state.draining.load(Ordering::Relaxed);
+    let ready = self_test.passed && !draining;
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    
+    (status, Json(ReadinessResponse {
+        status: if draining { "draining" } else if ready { "ready" } else { "unavailable" },
+        version: VERSION,
+        git_commit: GIT_COMMIT,
+        uptime_seconds: state.started_at.elapsed().as_secs(),
+        draining,
+        self_test,
+    }))
+}
+
+fn self_test() -> SelfTest {
+    let result = run_pipeline(SELF_TEST_PROGRAM, false, &all_stages());
+    let semantic_error = result.semantics.as_ref().and_then(|semantics| {
+        semantics.steps.iter().find_map(|step| step.error.clone())
+    });
+    let error = result.error.map(|error| error.message).or(semantic_error);
+    if let Some(error) = &error {
+        tracing::error!(%error, "self-test failed");
+    }
+    SelfTest {
+        passed: error.is_none(),
+        duration_ms: result.total_ms,
+        error,
+    }
+}
@@ -0,0 +1,293 @@
+//! Live analysis over a WebSocket.
+//!
+//! The client opens a document, then sends incremental edits as the user
+//! types. Edits are debounced by `analysis.live_debounce_ms`, and each burst
+//! reruns the compiler pipeline in the background. A new edit cancels the run
+//! still in flight, and results are only pushed for the latest version of the
+//! document.
+//!
+//! Messages are JSON objects tagged by `type`:
+//!
+//! - client: `{"type": "open", "version": 1, "code": "...", "stages": [...]}`
+//! - client: `{"type": "edit", "version": 2, "changes": [{"start": 0, "end": 1, "text": "y"}]}`
+//! - server: `{"type": "analysis", "version": 2, "diagnostics": [...], ...}` with
+//!   the same fields as an `/api/compile` response
+//! - server: `{"type": "error", "version": 2, "error": {"code": "invalid_edit", ...}}`
+//!
+//! Diagnostics and errors use the same shape as the `error` of HTTP responses.
+//!
+//! Change offsets count UTF-16 code units, like JavaScript string indices, and
+//! each change applies to the document left by the previous one.
+
+use crate::error::ErrorBody;
+use crate::{all_stages, run_pipeline_until, telemetry, AppState, PipelineResponse, Stage};
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::time::{sleep_until, Instant};
+use tracing::Instrument;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Open {
+        #[serde(default)]
+        version: u64,
+        code: String,
+        #[serde(default)]
+        include_trivia: bool,
+        #[serde(default = "all_stages")]
+        stages: Vec<Stage>,
+    },
+    Edit {
+        version: u64,
+        changes: Vec<TextChange>,
+    },
+}
+
+/// Replaces `start..end` of the document with `text`
+#[derive(Deserialize)]
+struct TextChange {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Analysis {
+        version: u64,
+        diagnostics: Vec<ErrorBody>,
+        #[serde(flatten)]
+        result: Box<PipelineResponse>,
+    },
+    Error {
+        version: Option<u64>,
+        error: ErrorBody,
+    },
+}
+
+/// Document state of one connection
+struct Session {
+    code: String,
+    version: u64,
+    include_trivia: bool,
+    stages: Vec<Stage>,
+    opened: bool,
+    max_source_bytes: usize,
+}
+
+impl Session {
+    fn apply(&mut self, message: ClientMessage) -> Result<(), ErrorBody> {
+        match message {
+            ClientMessage::Open { version, code, include_trivia, stages } => {
+                check_size(&code, self.max_source_bytes)?;
+                self.code = code;
+                self.version = version;
+                self.include_trivia = include_trivia;
+                self.stages = stages;
+                self.opened = true;
+            },
+            ClientMessage::Edit { version, changes } => {
+                if !self.opened {
+                    return Err(invalid_edit("edit received before open".to_string()));
+                }
+                if version <= self.version {
+                    return Err(invalid_edit(format!("edit version {} is not newer than {}", version, self.version)));
+                }
+                // Apply to a copy so a bad change leaves the document untouched
+                let mut code = self.code.clone();
+                for change in changes {
+                    let start = byte_offset(&code, change.start).map_err(invalid_edit)?;
+                    let end = byte_offset(&code, change.end).map_err(invalid_edit)?;
+                    if start > end {
+                        return Err(invalid_edit(format!("change range {}..{} is reversed", change.start, change.end)));
+                    }
+                    code.replace_range(start..end, &change.text);
+                }
+                check_size(&code, self.max_source_bytes)?;
+                self.code = code;
+                self.version = version;
+            },
+        }
+        Ok(())
+    }
+}
+
+fn invalid_edit(message: String) -> ErrorBody {
+    ErrorBody::new("invalid_edit", message)
+}
+
+fn check_size(code: &str, max_source_bytes: usize) -> Result<(), ErrorBody> {
+    if code.len() > max_source_bytes {
+        let message = format!("document is {} bytes, the limit is {}", code.len(), max_source_bytes);
+        return Err(ErrorBody::new("source_too_large", message));
+    }
+    Ok(())
+}
+
+/// Converts a UTF-16 offset into a byte offset of `text`
+fn byte_offset(text: &str, utf16_offset: usize) -> Result<usize, String> {
+    let mut units = 0;
+    for (byte, c) in text.char_indices() {
+        if units == utf16_offset {
+            return Ok(byte);
+        }
+        units += c.len_utf16();
+        if units > utf16_offset {
+            break;
+        }
+    }
+    if units == utf16_offset {
+        return Ok(text.len());
+    }
+    Err(format!("offset {} is outside the document or inside a character", utf16_offset))
+}
+
+pub fn diagnostics(result: &PipelineResponse) -> Vec<ErrorBody> {
+    let mut diagnostics: Vec<ErrorBody> = result.error.iter().cloned().collect();
+    if let Some(semantics) = &result.semantics {
+        let messages = semantics.steps.iter()
+            .filter_map(|step| step.error.as_ref())
+            .chain(semantics.type_checks.iter().filter_map(|check| check.error_message.as_ref()));
+        for message in messages {
+            if !diagnostics.iter().any(|d| &d.message == message) {
+                diagnostics.push(ErrorBody {
+                    stage: Some(Stage::Semantics),
+                    ..ErrorBody::new("semantic_error", message.clone())
+                });
+            }
+        }
+    }
+    diagnostics
+}
+
+async fn shutdown_started(shutdown: &mut watch::Receiver<bool>) {
+    // The returned guard is not `Send`, so it must not outlive this function
+    let _ = shutdown.wait_for(|started| *started).await;
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/live",
+    tag = "compiler",
+    description = "Upgrades to a WebSocket carrying the live-analysis protocol described in the module docs.",
+    responses((status = 101, description = "Switched to the WebSocket protocol"))
+)]
+pub async fn live_analysis(State(state): State<AppState>, ws: WebSocketUpgrade) -> Response {
+    // Keep the upgrade request's span (and its request ID) for the whole session
+    let span = tracing::Span::current();
+    ws.on_upgrade(move |socket| live_session(socket, state).instrument(span))
+}
+
+async fn live_session(mut socket: WebSocket, state: AppState) {
+    // Quiet period after the last edit before the pipeline reruns
+    let debounce = Duration::from_millis(state.config.analysis.live_debounce_ms);
+    let mut session = Session {
+        code: String::new(),
+        version: 0,
+        include_trivia: false,
+        stages: all_stages(),
+        opened: false,
+        max_source_bytes: state.config.analysis.max_source_bytes,
+    };
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel::<(u64, PipelineResponse)>();
+    // Bumped on every accepted message; tags runs so stale results can be told apart
+    let mut generation = 0u64;
+    // Set when an edit arrived and the pipeline has not been rerun for it yet
+    let mut deadline: Option<Instant> = None;
+    let mut running: Option<Arc<AtomicBool>> = None;
+    let mut shutdown = state.shutdown.clone();
+    tracing::info!("live session opened");
+    
+    loop {
+        let reply = tokio::select! {
+            incoming = socket.recv() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => continue,
+                };
+                let applied = serde_json::from_str::<ClientMessage>(&text)
+                    .map_err(|e| ErrorBody::new("invalid_message", e.to_string()))
+                    .and_then(|message| session.apply(message));
+                match applied {
+                    Ok(()) => {
+                        generation += 1;
+                        // The run in flight is for an older version now
+                        if let Some(cancelled) = running.take() {
+                            cancelled.store(true, Ordering::Relaxed);
+                        }
+                        deadline = Some(Instant::now() + debounce);
+                        None
+                    },
+                    Err(error) => {
+                        tracing::debug!(code = %error.code, message = %error.message, "rejected live message");
+                        Some(ServerMessage::Error { version: Some(session.version), error })
+                    },
+                }
+            },
+            _ = sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                deadline = None;
+                let cancelled = Arc::new(AtomicBool::new(false));
+                running = Some(cancelled.clone());
+                
+                let results = results_tx.clone();
+                let run = generation;
+                let code = session.code.clone();
+                let include_trivia = session.include_trivia;
+                let stages = session.stages.clone();
+                telemetry::record_source_size("live", code.len());
+                let span = tracing::Span::current();
+                tokio::task::spawn_blocking(move || {
+                    let _span = span.entered();
+                    if let Some(result) = run_pipeline_until(&code, include_trivia, &stages, &cancelled) {
+                        let _ = results.send((run, result));
+                    }
+                });
+                None
+            },
+            () = shutdown_started(&mut shutdown) => {
+                let _ = socket.send(Message::Close(Some(CloseFrame {
+                    code: close_code::AWAY,
+                    reason: "server shutting down".into(),
+                }))).await;
+                break;
+            },
+            Some((run, result)) = results_rx.recv() => {
+                // A run that finished just before being cancelled is stale too
+                if run != generation {
+                    continue;
+                }
+                running = None;
+                Some(ServerMessage::Analysis {
+                    version: session.version,
+                    diagnostics: diagnostics(&result),
+                    result: Box::new(result),
+                })
+            },
+        };
+        
+        if let Some(reply) = reply {
+            let json = serde_json::to_string(&reply).expect("server messages always serialize");
+            if socket.send(Message::Text(json)).await.is_err() {
+                break;
+            }
+        }
+    }
+    
+    if let Some(cancelled) = running {
+        cancelled.store(true, Ordering::Relaxed);
+    }
+    tracing::info!("live session closed");
+}
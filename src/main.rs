@@ -1,92 +1,188 @@
 use axum::{
-    extract::Json,
+    extract::{DefaultBodyLimit, State},
     http::{Method, StatusCode},
     response::Json as ResponseJson,
+    middleware,
     routing::{get, post},
     Router,
 };
-use compiler_core::lexer::lexer::{Lexer, TokenType};
-use compiler_core::parser::Parser;
+use compiler_core::lexer::lexer::{BalancingDepthType, Lexer, NumericHint, TokenCategory, TokenType, Trivia, TriviaToken};
+use compiler_core::parser::{stack_size_for, ParseError, Parser};
 use compiler_core::ast::ASTNode;
 use compiler_core::graphviz::GraphvizRenderer;
+use compiler_core::semantic::{analyze, SemanticStep, SymbolInfo, TypeCheck};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
 
-use tower_http::cors::{Any, CorsLayer};
+use tower::ServiceBuilder;
+use tower_http::catch_panic::CatchPanicLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
+use tracing::Level;
 
-#[derive(Deserialize)]
+mod config;
+mod error;
+mod examples;
+mod health;
+mod live;
+mod openapi;
+mod snippets;
+mod telemetry;
+
+use config::Config;
+use error::{ApiError, ApiJson, ApiQuery, ErrorBody, ErrorEnvelope};
+use examples::{Example, ExampleFilter};
+use snippets::SnippetStore;
+
+/// Shared by every handler
+#[derive(Clone)]
+struct AppState {
+    config: Arc<Config>,
+    examples: Arc<Vec<Example>>,
+    snippets: Arc<dyn SnippetStore>,
+    started_at: Instant,
+    /// Set once shutdown begins; readiness fails from then on
+    draining: Arc<AtomicBool>,
+    /// Flips to `true` when shutdown begins, so long-lived connections can close
+    shutdown: watch::Receiver<bool>,
+}
+
+impl AppState {
+    /// Records the program size and rejects programs over the configured analysis limit
+    fn check_source_size(&self, endpoint: &'static str, code: &str) -> Result<(), ApiError> {
+        telemetry::record_source_size(endpoint, code.len());
+        let limit = self.config.analysis.max_source_bytes;
+        if code.len() > limit {
+            return Err(ApiError::source_too_large(code.len(), limit));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
 struct CompileRequest {
     code: String,
+    #[serde(default)]
+    include_trivia: bool,
+}
+
+/// Compiler stages that `/api/compile` can run, in pipeline order
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug, ToSchema)]
+#[serde(rename_all = "snake_case")]
+enum Stage {
+    Tokens,
+    Ast,
+    Graph,
+    Semantics,
+}
+
+impl Stage {
+    const ALL: [Stage; 4] = [Stage::Tokens, Stage::Ast, Stage::Graph, Stage::Semantics];
+    
+    fn name(self) -> &'static str {
+        match self {
+            Stage::Tokens => "tokens",
+            Stage::Ast => "ast",
+            Stage::Graph => "graph",
+            Stage::Semantics => "semantics",
+        }
+    }
+}
+
+fn all_stages() -> Vec<Stage> {
+    Stage::ALL.to_vec()
+}
+
+#[derive(Deserialize, ToSchema)]
+struct PipelineRequest {
+    code: String,
+    #[serde(default)]
+    include_trivia: bool,
+    /// Stages whose output should be returned; all of them when omitted
+    #[serde(default = "all_stages")]
+    stages: Vec<Stage>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct TokenizeResponse {
     tokens: Vec<TokenInfo>,
-    success: bool,
-    error: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct TokenInfo {
-    token_type: String,
-    raw_value: String,
+    category: TokenCategory,
+    lexeme: String,
     position: usize,
+    /// Line of the token's first character, starting at 1
+    line: usize,
+    /// Column of the token's first character, starting at 1
+    column: usize,
+    numeric_kind: Option<NumericHint>,
+    #[schema(value_type = Option<i32>)]
+    bracket_depth: Option<BalancingDepthType>,
+    leading_trivia: Vec<Trivia>,
 }
 
-#[derive(Serialize)]
+impl TokenInfo {
+    fn new(trivia_token: TriviaToken, position: usize) -> Self {
+        TokenInfo {
+            category: trivia_token.token.category(),
+            numeric_kind: trivia_token.token.numeric_kind(),
+            bracket_depth: trivia_token.token.bracket_depth(),
+            lexeme: trivia_token.lexeme,
+            position,
+            line: trivia_token.line,
+            column: trivia_token.column,
+            leading_trivia: trivia_token.leading_trivia,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
 struct ParseResponse {
-    ast: Option<ASTNode>,
-    success: bool,
-    error: Option<String>,
+    ast: ASTNode,
 }
 
-#[derive(Serialize)]
-struct SemanticAnalysisResponse {
+#[derive(Serialize, ToSchema)]
+struct GraphData {
+    dot_content: String,
+    nodes: Vec<NodeData>,
+    edges: Vec<EdgeData>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct SemanticData {
     steps: Vec<SemanticStep>,
     symbol_table: Vec<SymbolInfo>,
     type_checks: Vec<TypeCheck>,
-    success: bool,
-    error: Option<String>,
 }
 
-#[derive(Serialize)]
-struct SemanticStep {
-    step_number: usize,
-    description: String,
-    node_type: String,
-    action: String,
-    symbol_added: Option<String>,
-    type_check: Option<String>,
-    error: Option<String>,
-}
-
-#[derive(Serialize)]
-struct SymbolInfo {
-    name: String,
-    symbol_type: String,
-    data_type: String,
-    scope: String,
-    line: usize,
+#[derive(Serialize, ToSchema)]
+struct StageTiming {
+    stage: Stage,
+    duration_ms: f64,
 }
 
-#[derive(Serialize)]
-struct TypeCheck {
-    expression: String,
-    expected_type: String,
-    actual_type: String,
-    is_valid: bool,
-    error_message: Option<String>,
-}
-
-#[derive(Serialize)]
-struct VisualizationResponse {
-    dot_content: String,
-    nodes: Vec<NodeData>,
-    edges: Vec<EdgeData>,
-    success: bool,
-    error: Option<String>,
+/// Outputs of a single pipeline run; stages that were not requested (or not
+/// reached because an earlier stage failed) are `null`. `error` describes the
+/// stage that failed, if any.
+#[derive(Serialize, ToSchema)]
+struct PipelineResponse {
+    tokens: Option<Vec<TokenInfo>>,
+    ast: Option<ASTNode>,
+    graph: Option<GraphData>,
+    semantics: Option<SemanticData>,
+    timings: Vec<StageTiming>,
+    total_ms: f64,
+    error: Option<ErrorBody>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct NodeData {
     id: String,
     label: String,
@@ -94,171 +190,289 @@ struct NodeData {
     color: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct EdgeData {
     from: String,
     to: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct ExampleResponse {
     examples: Vec<Example>,
 }
 
-#[derive(Serialize)]
-struct Example {
-    name: String,
-    code: String,
-    description: String,
-    category: String,
-}
-
 async fn health_check() -> &'static str {
     "Compiler Backend is running!"
 }
 
-async fn tokenize(Json(request): Json<CompileRequest>) -> Result<ResponseJson<TokenizeResponse>, StatusCode> {
-    let mut lexer = Lexer::new(&request.code);
+/// Lexes the whole input; on a lexer error the tokens read so far are returned with it
+fn collect_tokens(code: &str, include_trivia: bool) -> (Vec<TokenInfo>, Option<ErrorBody>) {
+    let mut lexer = Lexer::new(code).keep_trivia(include_trivia);
     let mut tokens = Vec::new();
     let mut position = 0;
     
     loop {
-        match lexer.next_token() {
-            Ok(TokenType::EOF) => {
-                tokens.push(TokenInfo {
-                    token_type: "EOF".to_string(),
-                    raw_value: "".to_string(),
-                    position,
-                });
-                break;
-            },
-            Ok(token) => {
-                tokens.push(TokenInfo {
-                    token_type: format!("{:?}", token).split('{').next().unwrap_or("Unknown").to_string(),
-                    raw_value: format!("{:?}", token),
-                    position,
-                });
+        match lexer.next_token_with_trivia() {
+            Ok(trivia_token) => {
+                let at_end = trivia_token.token == TokenType::EOF;
+                tokens.push(TokenInfo::new(trivia_token, position));
+                if at_end {
+                    return (tokens, None);
+                }
                 position += 1;
             },
             Err(e) => {
-                return Ok(ResponseJson(TokenizeResponse {
-                    tokens,
-                    success: false,
-                    error: Some(format!("{:?}", e)),
-                }));
-            }
+                telemetry::record_error(e.kind());
+                let position = e.position().unwrap_or((lexer.cur_line, lexer.cur_col));
+                return (tokens, Some(ErrorBody::compile(Stage::Tokens, e.kind(), e.to_string(), position)));
+            },
         }
     }
-    
-    Ok(ResponseJson(TokenizeResponse {
-        tokens,
-        success: true,
-        error: None,
-    }))
-}
-
-async fn parse(Json(request): Json<CompileRequest>) -> Result<ResponseJson<ParseResponse>, StatusCode> {
-    match Parser::new(&request.code) {
-        Ok(mut parser) => {
-            match parser.parse() {
-                Ok(ast) => Ok(ResponseJson(ParseResponse {
-                    ast: Some(ast),
-                    success: true,
-                    error: None,
-                })),
-                Err(e) => Ok(ResponseJson(ParseResponse {
-                    ast: None,
-                    success: false,
-                    error: Some(format!("{:?}", e)),
-                })),
-            }
+}
+
+fn parse_code(code: &str) -> Result<ASTNode, ErrorBody> {
+    let report = |e: ParseError, position| {
+        telemetry::record_error(e.kind());
+        ErrorBody::compile(Stage::Ast, e.kind(), e.to_string(), position)
+    };
+    // `Parser::new` only fails on the first token, and lexer errors carry their own position
+    let mut parser = Parser::new(code).map_err(|e| {
+        let position = e.position().unwrap_or((1, 1));
+        report(e, position)
+    })?;
+    parser.parse().map_err(|e| report(e, parser.position()))
+}
+
+fn analyze_program(ast: &ASTNode) -> SemanticData {
+    let analysis = analyze(ast);
+    for _ in analysis.steps.iter().filter(|step| step.error.is_some()) {
+        telemetry::record_error("semantic");
+    }
+    SemanticData {
+        steps: analysis.steps,
+        symbol_table: analysis.symbol_table,
+        type_checks: analysis.type_checks,
+    }
+}
+
+/// Runs one compiler stage inside a tracing span and records how long it
+/// took; returns the output with the duration in milliseconds
+fn timed_stage<T>(stage: Stage, run: impl FnOnce() -> T) -> (T, f64) {
+    let _span = tracing::info_span!("stage", stage = stage.name()).entered();
+    let start = Instant::now();
+    let output = run();
+    let seconds = start.elapsed().as_secs_f64();
+    telemetry::record_stage(stage.name(), seconds);
+    tracing::debug!(duration_ms = seconds * 1000.0, "stage finished");
+    (output, seconds * 1000.0)
+}
+
+fn build_graph(ast: &ASTNode) -> GraphData {
+    let dot_content = GraphvizRenderer::new().render_to_dot(ast);
+    let (nodes, edges) = generate_visualization_data(ast);
+    GraphData { dot_content, nodes, edges }
+}
+
+/// Runs compiler work on the blocking thread pool, as live sessions do, so a
+/// large program cannot stall the async workers. A panic is resumed here, so
+/// `CatchPanicLayer` still answers it.
+async fn run_blocking<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Result<T, ApiError> {
+    let span = tracing::Span::current();
+    let task = tokio::task::spawn_blocking(move || {
+        let _span = span.entered();
+        work()
+    });
+    match task.await {
+        Ok(output) => Ok(output),
+        Err(e) => match e.try_into_panic() {
+            Ok(panic) => std::panic::resume_unwind(panic),
+            Err(e) => {
+                tracing::error!(error = %e, "compiler task failed");
+                Err(ApiError::internal())
+            },
         },
-        Err(e) => Ok(ResponseJson(ParseResponse {
-            ast: None,
-            success: false,
-            error: Some(format!("{:?}", e)),
-        })),
     }
 }
 
-async fn visualize(Json(request): Json<CompileRequest>) -> Result<ResponseJson<VisualizationResponse>, StatusCode> {
-    match Parser::new(&request.code) {
-        Ok(mut parser) => {
-            match parser.parse() {
-                Ok(ast) => {
-                    let mut renderer = GraphvizRenderer::new();
-                    let dot_content = renderer.render_to_dot(&ast);
-                    
-                    // Generate simplified node/edge data for frontend
-                    let (nodes, edges) = generate_visualization_data(&ast);
-                    
-                    Ok(ResponseJson(VisualizationResponse {
-                        dot_content,
-                        nodes,
-                        edges,
-                        success: true,
-                        error: None,
-                    }))
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Runs the requested stages over `code`, parsing at most once. The token
+/// stage and the parser each lex the source, since the parser reads from its
+/// own lexer. Later stages reuse the AST of the parse stage, which always runs
+/// when any stage after it is requested; a failing stage stops the pipeline.
+fn run_pipeline(code: &str, include_trivia: bool, stages: &[Stage]) -> PipelineResponse {
+    run_pipeline_until(code, include_trivia, stages, &AtomicBool::new(false))
+        .expect("pipeline without cancellation always completes")
+}
+
+/// Same as `run_pipeline`, but gives up between stages once `cancelled` is set
+fn run_pipeline_until(code: &str, include_trivia: bool, stages: &[Stage], cancelled: &AtomicBool) -> Option<PipelineResponse> {
+    let pipeline_start = Instant::now();
+    let mut response = PipelineResponse {
+        tokens: None,
+        ast: None,
+        graph: None,
+        semantics: None,
+        timings: Vec::new(),
+        total_ms: 0.0,
+        error: None,
+    };
+    
+    let mut ast = None;
+    for stage in Stage::ALL {
+        let needed = match stage {
+            Stage::Tokens => stages.contains(&Stage::Tokens),
+            // The AST feeds every later stage
+            Stage::Ast => stages.iter().any(|s| *s != Stage::Tokens),
+            Stage::Graph | Stage::Semantics => stages.contains(&stage),
+        };
+        if !needed {
+            continue;
+        }
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        
+        let (error, duration_ms) = timed_stage(stage, || match stage {
+            Stage::Tokens => {
+                let (tokens, error) = collect_tokens(code, include_trivia);
+                response.tokens = Some(tokens);
+                error
+            },
+            Stage::Ast => match parse_code(code) {
+                Ok(tree) => {
+                    ast = Some(tree);
+                    None
                 },
-                Err(e) => Ok(ResponseJson(VisualizationResponse {
-                    dot_content: String::new(),
-                    nodes: Vec::new(),
-                    edges: Vec::new(),
-                    success: false,
-                    error: Some(format!("{:?}", e)),
-                })),
-            }
-        },
-        Err(e) => Ok(ResponseJson(VisualizationResponse {
-            dot_content: String::new(),
-            nodes: Vec::new(),
-            edges: Vec::new(),
-            success: false,
-            error: Some(format!("{:?}", e)),
-        })),
+                Err(e) => Some(e),
+            },
+            Stage::Graph => {
+                response.graph = ast.as_ref().map(build_graph);
+                None
+            },
+            Stage::Semantics => {
+                response.semantics = ast.as_ref().map(analyze_program);
+                None
+            },
+        });
+        response.timings.push(StageTiming { stage, duration_ms });
+        
+        if error.is_some() {
+            response.error = error;
+            break;
+        }
+    }
+    
+    if stages.contains(&Stage::Ast) {
+        response.ast = ast;
     }
+    response.total_ms = elapsed_ms(pipeline_start);
+    Some(response)
 }
 
-async fn get_examples() -> ResponseJson<ExampleResponse> {
-    let examples = vec![
-        Example {
-            name: "String Assignment".to_string(),
-            code: r#"id2 = "Mi nombre es Levi""#.to_string(),
-            description: "Simple string assignment".to_string(),
-            category: "basic".to_string(),
-        },
-        Example {
-            name: "Arithmetic Expression".to_string(),
-            code: "x = 5 + 3 * 2".to_string(),
-            description: "Arithmetic with operator precedence".to_string(),
-            category: "arithmetic".to_string(),
-        },
-        Example {
-            name: "Parenthesized Expression".to_string(),
-            code: "result = (a + b) * c".to_string(),
-            description: "Parenthesized expressions".to_string(),
-            category: "arithmetic".to_string(),
-        },
-        Example {
-            name: "Function Call".to_string(),
-            code: "func(x, y + 1)".to_string(),
-            description: "Function calls with arguments".to_string(),
-            category: "functions".to_string(),
-        },
-        Example {
-            name: "Logical Operations".to_string(),
-            code: "a > b && c <= d".to_string(),
-            description: "Logical operations".to_string(),
-            category: "logical".to_string(),
-        },
-        Example {
-            name: "Multiple Statements".to_string(),
-            code: "x = 10; y = 20; result = x + y".to_string(),
-            description: "Multiple statements".to_string(),
-            category: "advanced".to_string(),
-        },
-    ];
+#[utoipa::path(
+    post,
+    path = "/api/v1/compile",
+    tag = "compiler",
+    request_body = PipelineRequest,
+    responses(
+        (status = 200, description = "Outputs of the requested stages with per-stage timings", body = PipelineResponse),
+        (status = 422, description = "A stage failed; outputs up to it, with `error` set", body = PipelineResponse),
+        (status = 400, description = "Body is not valid JSON", body = ErrorEnvelope),
+        (status = 413, description = "Program larger than `analysis.max_source_bytes`", body = ErrorEnvelope),
+        (status = 415, description = "Missing `Content-Type: application/json`", body = ErrorEnvelope),
+    )
+)]
+async fn compile(State(state): State<AppState>, ApiJson(request): ApiJson<PipelineRequest>) -> Result<(StatusCode, ResponseJson<PipelineResponse>), ApiError> {
+    state.check_source_size("compile", &request.code)?;
+    let response = run_blocking(move || run_pipeline(&request.code, request.include_trivia, &request.stages)).await?;
+    let status = if response.error.is_some() { StatusCode::UNPROCESSABLE_ENTITY } else { StatusCode::OK };
+    Ok((status, ResponseJson(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/tokenize",
+    tag = "compiler",
+    request_body = CompileRequest,
+    responses(
+        (status = 200, description = "Tokens of the program", body = TokenizeResponse),
+        (status = 422, description = "Program does not compile", body = ErrorEnvelope),
+        (status = 400, description = "Body is not valid JSON", body = ErrorEnvelope),
+        (status = 413, description = "Program larger than `analysis.max_source_bytes`", body = ErrorEnvelope),
+        (status = 415, description = "Missing `Content-Type: application/json`", body = ErrorEnvelope),
+    )
+)]
+async fn tokenize(State(state): State<AppState>, ApiJson(request): ApiJson<CompileRequest>) -> Result<ResponseJson<TokenizeResponse>, ApiError> {
+    state.check_source_size("tokenize", &request.code)?;
+    let ((tokens, error), _) = run_blocking(move || {
+        timed_stage(Stage::Tokens, || collect_tokens(&request.code, request.include_trivia))
+    }).await?;
     
+    match error {
+        Some(error) => Err(ApiError::compile(error)),
+        None => Ok(ResponseJson(TokenizeResponse { tokens })),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/parse",
+    tag = "compiler",
+    request_body = CompileRequest,
+    responses(
+        (status = 200, description = "Abstract syntax tree of the program", body = ParseResponse),
+        (status = 422, description = "Program does not compile", body = ErrorEnvelope),
+        (status = 400, description = "Body is not valid JSON", body = ErrorEnvelope),
+        (status = 413, description = "Program larger than `analysis.max_source_bytes`", body = ErrorEnvelope),
+        (status = 415, description = "Missing `Content-Type: application/json`", body = ErrorEnvelope),
+    )
+)]
+async fn parse(State(state): State<AppState>, ApiJson(request): ApiJson<CompileRequest>) -> Result<ResponseJson<ParseResponse>, ApiError> {
+    state.check_source_size("parse", &request.code)?;
+    let (ast, _) = run_blocking(move || timed_stage(Stage::Ast, || parse_code(&request.code))).await?;
+    Ok(ResponseJson(ParseResponse { ast: ast.map_err(ApiError::compile)? }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/visualize",
+    tag = "compiler",
+    request_body = CompileRequest,
+    responses(
+        (status = 200, description = "Graphviz DOT and node/edge data for the AST", body = GraphData),
+        (status = 422, description = "Program does not compile", body = ErrorEnvelope),
+        (status = 400, description = "Body is not valid JSON", body = ErrorEnvelope),
+        (status = 413, description = "Program larger than `analysis.max_source_bytes`", body = ErrorEnvelope),
+        (status = 415, description = "Missing `Content-Type: application/json`", body = ErrorEnvelope),
+    )
+)]
+async fn visualize(State(state): State<AppState>, ApiJson(request): ApiJson<CompileRequest>) -> Result<ResponseJson<GraphData>, ApiError> {
+    state.check_source_size("visualize", &request.code)?;
+    let graph = run_blocking(move || {
+        let ast = timed_stage(Stage::Ast, || parse_code(&request.code)).0?;
+        Ok(timed_stage(Stage::Graph, || build_graph(&ast)).0)
+    }).await?;
+    Ok(ResponseJson(graph.map_err(ApiError::compile)?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/examples",
+    tag = "compiler",
+    params(ExampleFilter),
+    responses(
+        (status = 200, description = "Example programs matching the filter", body = ExampleResponse),
+        (status = 400, description = "Invalid query parameter", body = ErrorEnvelope),
+    )
+)]
+async fn get_examples(State(state): State<AppState>, ApiQuery(filter): ApiQuery<ExampleFilter>) -> ResponseJson<ExampleResponse> {
+    let examples = state.examples.iter()
+        .filter(|example| filter.matches(example))
+        .cloned()
+        .collect();
     ResponseJson(ExampleResponse { examples })
 }
 
@@ -280,12 +494,22 @@ fn generate_visualization_data(ast: &ASTNode) -> (Vec<NodeData>, Vec<EdgeData>)
         let (label, color) = match node {
             ASTNode::Number { value, .. } => (value.clone(), "#FFE4B5".to_string()),
             ASTNode::String { value } => (format!("\"{}\"", value), "#E6E6FA".to_string()),
+            ASTNode::Char { value } => (format!("'{}'", value.escape_default()), "#FFDAB9".to_string()),
             ASTNode::Boolean { value } => (value.to_string(), "#90EE90".to_string()),
             ASTNode::Identifier { name } => (name.clone(), "#B0E0E6".to_string()),
             ASTNode::BinaryOp { operator, .. } => (operator.clone(), "#FFB6C1".to_string()),
             ASTNode::UnaryOp { operator, .. } => (operator.clone(), "#DDA0DD".to_string()),
             ASTNode::Assignment { .. } => ("=".to_string(), "#98FB98".to_string()),
-            ASTNode::FunctionCall { name, .. } => (format!("{}()", name), "#F0E68C".to_string()),
+            ASTNode::MultipleAssignment { .. } => ("=".to_string(), "#3CB371".to_string()),
+            ASTNode::IncDec { operator, .. } => (operator.clone(), "#FFDEAD".to_string()),
+            ASTNode::CompoundAssignment { operator, .. } => (operator.clone(), "#F4A460".to_string()),
+            ASTNode::FunctionCall { .. } => ("call()".to_string(), "#F0E68C".to_string()),
+            ASTNode::CompositeLiteral { literal_type, .. } => (format!("{}{{}}", literal_type), "#AFEEEE".to_string()),
+            ASTNode::Index { .. } => ("[i]".to_string(), "#D8BFD8".to_string()),
+            ASTNode::Slice { .. } => ("[lo:hi]".to_string(), "#EE82EE".to_string()),
+            ASTNode::KeyValue { .. } => (":".to_string(), "#F0FFF0".to_string()),
+            ASTNode::FieldAccess { field, .. } => (format!(".{}", field), "#FFE4E1".to_string()),
+            ASTNode::TypeDeclaration { name, .. } => (format!("type {}", name), "#FAFAD2".to_string()),
             ASTNode::Parenthesized { .. } => ("( )".to_string(), "#D3D3D3".to_string()),
             ASTNode::Program { .. } => ("Program".to_string(), "#FFA07A".to_string()),
             ASTNode::ExpressionStatement { .. } => ("Statement".to_string(), "#20B2AA".to_string()),
@@ -318,11 +542,46 @@ fn generate_visualization_data(ast: &ASTNode) -> (Vec<NodeData>, Vec<EdgeData>)
                 traverse_ast(left, Some(node_id.clone()), nodes, edges, counter);
                 traverse_ast(right, Some(node_id.clone()), nodes, edges, counter);
             },
-            ASTNode::FunctionCall { arguments, .. } => {
+            ASTNode::MultipleAssignment { targets, values } => {
+                for child in targets.iter().chain(values) {
+                    traverse_ast(child, Some(node_id.clone()), nodes, edges, counter);
+                }
+            },
+            ASTNode::IncDec { target, .. } => {
+                traverse_ast(target, Some(node_id.clone()), nodes, edges, counter);
+            },
+            ASTNode::CompoundAssignment { target, value, .. } => {
+                traverse_ast(target, Some(node_id.clone()), nodes, edges, counter);
+                traverse_ast(value, Some(node_id.clone()), nodes, edges, counter);
+            },
+            ASTNode::FunctionCall { callee, arguments } => {
+                traverse_ast(callee, Some(node_id.clone()), nodes, edges, counter);
                 for arg in arguments {
                     traverse_ast(arg, Some(node_id.clone()), nodes, edges, counter);
                 }
             },
+            ASTNode::CompositeLiteral { elements, .. } => {
+                for element in elements {
+                    traverse_ast(element, Some(node_id.clone()), nodes, edges, counter);
+                }
+            },
+            ASTNode::Index { target, index } => {
+                traverse_ast(target, Some(node_id.clone()), nodes, edges, counter);
+                traverse_ast(index, Some(node_id.clone()), nodes, edges, counter);
+            },
+            ASTNode::Slice { target, low, high } => {
+                traverse_ast(target, Some(node_id.clone()), nodes, edges, counter);
+                for bound in low.iter().chain(high) {
+                    traverse_ast(bound, Some(node_id.clone()), nodes, edges, counter);
+                }
+            },
+            ASTNode::KeyValue { key, value } => {
+                traverse_ast(key, Some(node_id.clone()), nodes, edges, counter);
+                traverse_ast(value, Some(node_id.clone()), nodes, edges, counter);
+            },
+            ASTNode::FieldAccess { target, .. } => {
+                traverse_ast(target, Some(node_id.clone()), nodes, edges, counter);
+            },
             ASTNode::Parenthesized { expression } => {
                 traverse_ast(expression, Some(node_id.clone()), nodes, edges, counter);
             },
@@ -344,454 +603,176 @@ fn generate_visualization_data(ast: &ASTNode) -> (Vec<NodeData>, Vec<EdgeData>)
     (nodes, edges)
 }
 
-// Helper function to infer type from AST node
-fn infer_type_from_node(node: &ASTNode, symbol_table: &[SymbolInfo]) -> String {
-    match node {
-        ASTNode::Number { is_float, .. } => {
-            if *is_float {
-                "float64".to_string() // Go's default float type
-            } else {
-                "int".to_string() // Go's default int type
-            }
-        },
-        ASTNode::String { .. } => "string".to_string(),
-        ASTNode::Boolean { .. } => "bool".to_string(),
-        ASTNode::Identifier { name } => {
-            // Try to find the symbol in the symbol table
-            if let Some(symbol) = symbol_table.iter().find(|sym| sym.name == *name) {
-                symbol.data_type.clone()
-            } else {
-                "unknown".to_string()
-            }
-        },
-        ASTNode::BinaryOp { operator, left, right } => {
-            let left_type = infer_type_from_node(left, symbol_table);
-            let right_type = infer_type_from_node(right, symbol_table);
-            
-            match operator.as_str() {
-                "+" | "-" | "*" | "/" | "%" => {
-                    if left_type == "float64" || right_type == "float64" {
-                        "float64".to_string()
-                    } else {
-                        "int".to_string()
-                    }
-                },
-                "==" | "!=" | "<" | ">" | "<=" | ">=" => "bool".to_string(), // Comparison operations
-                "&&" | "||" => "bool".to_string(), // Logical operations
-                _ => "unknown".to_string(),
-            }
-        },
-        ASTNode::UnaryOp { operator, operand } => {
-            let operand_type = infer_type_from_node(operand, symbol_table);
-            
-            match operator.as_str() {
-                "!" => {
-                    if operand_type == "bool" {
-                        "bool".to_string()
-                    } else {
-                        "unknown".to_string()
-                    }
-                },
-                "-" | "+" => {
-                    if operand_type == "int" || operand_type == "float64" {
-                        operand_type
-                    } else {
-                        "unknown".to_string()
-                    }
-                },
-                _ => "unknown".to_string(),
-            }
-        },
-        ASTNode::FunctionCall { .. } => "unknown".to_string(), // Function return type unknown
-        ASTNode::Parenthesized { expression } => infer_type_from_node(expression, symbol_table),
-        _ => "unknown".to_string(),
-    }
+#[utoipa::path(
+    post,
+    path = "/api/v1/semantic-analysis",
+    tag = "compiler",
+    request_body = CompileRequest,
+    responses(
+        (status = 200, description = "Semantic analysis steps, symbol table and type checks", body = SemanticData),
+        (status = 422, description = "Program does not compile", body = ErrorEnvelope),
+        (status = 400, description = "Body is not valid JSON", body = ErrorEnvelope),
+        (status = 413, description = "Program larger than `analysis.max_source_bytes`", body = ErrorEnvelope),
+        (status = 415, description = "Missing `Content-Type: application/json`", body = ErrorEnvelope),
+    )
+)]
+async fn semantic_analysis(State(state): State<AppState>, ApiJson(request): ApiJson<CompileRequest>) -> Result<ResponseJson<SemanticData>, ApiError> {
+    state.check_source_size("semantic-analysis", &request.code)?;
+    let analysis = run_blocking(move || {
+        // First, parse the AST
+        let ast = timed_stage(Stage::Ast, || parse_code(&request.code)).0?;
+        Ok(timed_stage(Stage::Semantics, || analyze_program(&ast)).0)
+    }).await?;
+    Ok(ResponseJson(analysis.map_err(ApiError::compile)?))
 }
 
-async fn semantic_analysis(Json(request): Json<CompileRequest>) -> Result<ResponseJson<SemanticAnalysisResponse>, StatusCode> {
-    let mut steps = Vec::new();
-    let mut symbol_table = Vec::new();
-    let mut type_checks = Vec::new();
-    let mut step_number = 1;
-    
-    // First, parse the AST
-    let ast = match Parser::new(&request.code) {
-        Ok(mut parser) => {
-            match parser.parse() {
-                Ok(ast) => ast,
-                Err(e) => {
-                    return Ok(ResponseJson(SemanticAnalysisResponse {
-                        steps,
-                        symbol_table,
-                        type_checks,
-                        success: false,
-                        error: Some(format!("Error parsing: {:?}", e)),
-                    }));
-                }
-            }
-        },
+fn main() {
+    let config = match Config::load() {
+        Ok(config) => config,
         Err(e) => {
-            return Ok(ResponseJson(SemanticAnalysisResponse {
-                steps,
-                symbol_table,
-                type_checks,
-                success: false,
-                error: Some(format!("Error creating parser: {:?}", e)),
-            }));
+            eprintln!("Invalid configuration: {}", e);
+            std::process::exit(1);
         }
     };
-    
-    // Step 1: Initialize semantic analysis
-    steps.push(SemanticStep {
-        step_number,
-        description: "Iniciando análisis semántico".to_string(),
-        node_type: "Program".to_string(),
-        action: "Crear tabla de símbolos global".to_string(),
-        symbol_added: None,
-        type_check: None,
-        error: None,
-    });
-    step_number += 1;
-    
-    // Step 2: Analyze AST nodes
-    fn analyze_node(node: &ASTNode, steps: &mut Vec<SemanticStep>, symbol_table: &mut Vec<SymbolInfo>, 
-                   type_checks: &mut Vec<TypeCheck>, step_number: &mut usize) {
-        match node {
-            ASTNode::Identifier { name } => {
-                // Check if identifier is declared
-                let is_declared = symbol_table.iter().any(|sym| sym.name == *name);
-                if !is_declared {
-                    steps.push(SemanticStep {
-                        step_number: *step_number,
-                        description: format!("Variable '{}' no declarada", name),
-                        node_type: "Identifier".to_string(),
-                        action: "Verificar declaración".to_string(),
-                        symbol_added: None,
-                        type_check: None,
-                        error: Some(format!("Variable '{}' no está declarada", name)),
-                    });
-                } else {
-                    // Find the symbol to get its type
-                    let symbol = symbol_table.iter().find(|sym| sym.name == *name);
-                    let data_type = symbol.map(|s| s.data_type.clone()).unwrap_or_else(|| "unknown".to_string());
-                    
-                    steps.push(SemanticStep {
-                        step_number: *step_number,
-                        description: format!("Variable '{}' encontrada en tabla de símbolos", name),
-                        node_type: "Identifier".to_string(),
-                        action: "Verificar declaración".to_string(),
-                        symbol_added: None,
-                        type_check: Some(data_type),
-                        error: None,
-                    });
-                }
-                *step_number += 1;
-            },
-            
-            ASTNode::Assignment { left, right } => {
-                steps.push(SemanticStep {
-                    step_number: *step_number,
-                    description: "Analizando asignación".to_string(),
-                    node_type: "Assignment".to_string(),
-                    action: "Verificar tipos de asignación".to_string(),
-                    symbol_added: None,
-                    type_check: Some("Assignment check".to_string()),
-                    error: None,
-                });
-                *step_number += 1;
-                
-                // Analyze left side (should be identifier)
-                if let ASTNode::Identifier { name } = &**left {
-                    // Determine type from right side
-                    let right_type = infer_type_from_node(right, symbol_table);
-                    
-                    // Add to symbol table if not exists
-                    if !symbol_table.iter().any(|sym| sym.name == *name) {
-                        symbol_table.push(SymbolInfo {
-                            name: name.clone(),
-                            symbol_type: "Variable".to_string(),
-                            data_type: right_type.clone(),
-                            scope: "Global".to_string(),
-                            line: 1,
-                        });
-                        
-                        steps.push(SemanticStep {
-                            step_number: *step_number,
-                            description: format!("Variable '{}' agregada a tabla de símbolos con tipo {}", name, right_type),
-                            node_type: "Identifier".to_string(),
-                            action: "Agregar a tabla de símbolos".to_string(),
-                            symbol_added: Some(name.clone()),
-                            type_check: Some(right_type),
-                            error: None,
-                        });
-                        *step_number += 1;
-                    } else {
-                        // Update existing symbol type if needed
-                        if let Some(symbol) = symbol_table.iter_mut().find(|sym| sym.name == *name) {
-                            if symbol.data_type == "Unknown" {
-                                symbol.data_type = right_type.clone();
-                                steps.push(SemanticStep {
-                                    step_number: *step_number,
-                                    description: format!("Tipo de variable '{}' actualizado a {}", name, right_type),
-                                    node_type: "Identifier".to_string(),
-                                    action: "Actualizar tipo en tabla de símbolos".to_string(),
-                                    symbol_added: None,
-                                    type_check: Some(right_type),
-                                    error: None,
-                                });
-                                *step_number += 1;
-                            }
-                        }
-                    }
-                }
-                
-                // Analyze right side
-                analyze_node(right, steps, symbol_table, type_checks, step_number);
-            },
-            
-            ASTNode::UnaryOp { operator, operand } => {
-                let operand_type = infer_type_from_node(operand, symbol_table);
-                let result_type = match operator.as_str() {
-                    "!" => {
-                        if operand_type == "bool" {
-                            "bool".to_string()
-                        } else {
-                            "unknown".to_string()
-                        }
-                    },
-                    "-" | "+" => {
-                        if operand_type == "int" || operand_type == "float64" {
-                            operand_type.clone()
-                        } else {
-                            "unknown".to_string()
-                        }
-                    },
-                    _ => "unknown".to_string(),
-                };
-                
-                let is_valid = match operator.as_str() {
-                    "!" => operand_type == "bool",
-                    "-" | "+" => operand_type == "int" || operand_type == "float64",
-                    _ => true,
-                };
-                
-                steps.push(SemanticStep {
-                    step_number: *step_number,
-                    description: format!("Analizando operación unaria: {} (operando: {})", operator, operand_type),
-                    node_type: "UnaryOp".to_string(),
-                    action: "Verificar tipo de operando".to_string(),
-                    symbol_added: None,
-                    type_check: Some(format!("Resultado: {}", result_type)),
-                    error: None,
-                });
-                *step_number += 1;
-                
-                // Add type check
-                type_checks.push(TypeCheck {
-                    expression: format!("{}{}", 
-                        operator,
-                        match &**operand {
-                            ASTNode::Identifier { name } => name.clone(),
-                            ASTNode::Number { value, .. } => value.clone(),
-                            ASTNode::Boolean { value } => value.to_string(),
-                            _ => "expr".to_string(),
-                        }
-                    ),
-                    expected_type: result_type.clone(),
-                    actual_type: result_type,
-                    is_valid,
-                    error_message: if !is_valid {
-                        Some(format!("Operación {} no válida para tipo {}", operator, operand_type))
-                    } else {
-                        None
-                    },
-                });
-                
-                analyze_node(operand, steps, symbol_table, type_checks, step_number);
-            },
-            
-            ASTNode::BinaryOp { left, operator, right } => {
-                let left_type = infer_type_from_node(left, symbol_table);
-                let right_type = infer_type_from_node(right, symbol_table);
-                let result_type = match operator.as_str() {
-                    "+" | "-" | "*" | "/" | "%" => {
-                        if left_type == "float64" || right_type == "float64" {
-                            "float64".to_string()
-                        } else {
-                            "int".to_string()
-                        }
-                    },
-                    "==" | "!=" | "<" | ">" | "<=" | ">=" => "bool".to_string(),
-                    "&&" | "||" => "bool".to_string(),
-                    _ => "unknown".to_string(),
-                };
-                
-                steps.push(SemanticStep {
-                    step_number: *step_number,
-                    description: format!("Analizando operación binaria: {} ({} {} {})", operator, left_type, operator, right_type),
-                    node_type: "BinaryOp".to_string(),
-                    action: "Verificar tipos de operandos".to_string(),
-                    symbol_added: None,
-                    type_check: Some(format!("Resultado: {}", result_type)),
-                    error: None,
-                });
-                *step_number += 1;
-                
-                // Add type check
-                let is_valid = match operator.as_str() {
-                    "+" | "-" | "*" | "/" | "%" => {
-                        left_type == "int" || left_type == "float64" || left_type == "float32" ||
-                        right_type == "int" || right_type == "float64" || right_type == "float32"
-                    },
-                    "==" | "!=" | "<" | ">" | "<=" | ">=" => {
-                        left_type == "int" || left_type == "float64" || left_type == "float32" || left_type == "string" || left_type == "bool" ||
-                        right_type == "int" || right_type == "float64" || right_type == "float32" || right_type == "string" || right_type == "bool"
-                    },
-                    "&&" | "||" => {
-                        left_type == "bool" && right_type == "bool"
-                    },
-                    _ => true,
-                };
-                
-                type_checks.push(TypeCheck {
-                    expression: format!("{} {} {}", 
-                        match &**left {
-                            ASTNode::Identifier { name } => name.clone(),
-                            ASTNode::Number { value, .. } => value.clone(),
-                            _ => "expr".to_string(),
-                        },
-                        operator,
-                        match &**right {
-                            ASTNode::Identifier { name } => name.clone(),
-                            ASTNode::Number { value, .. } => value.clone(),
-                            _ => "expr".to_string(),
-                        }
-                    ),
-                    expected_type: result_type.clone(),
-                    actual_type: result_type,
-                    is_valid,
-                    error_message: if !is_valid {
-                        Some(format!("Tipos incompatibles: {} {} {}", left_type, operator, right_type))
-                    } else {
-                        None
-                    },
-                });
-                
-                analyze_node(left, steps, symbol_table, type_checks, step_number);
-                analyze_node(right, steps, symbol_table, type_checks, step_number);
-            },
-            
-            ASTNode::Number { value, is_float } => {
-                let go_type = if *is_float { "float64" } else { "int" };
-                steps.push(SemanticStep {
-                    step_number: *step_number,
-                    description: format!("Literal numérico: {} (tipo: {})", value, go_type),
-                    node_type: "Number".to_string(),
-                    action: "Verificar tipo numérico".to_string(),
-                    symbol_added: None,
-                    type_check: Some(go_type.to_string()),
-                    error: None,
-                });
-                *step_number += 1;
-            },
-            
-            ASTNode::String { value } => {
-                steps.push(SemanticStep {
-                    step_number: *step_number,
-                    description: format!("Literal de cadena: \"{}\" (tipo: string)", value),
-                    node_type: "String".to_string(),
-                    action: "Verificar tipo string".to_string(),
-                    symbol_added: None,
-                    type_check: Some("string".to_string()),
-                    error: None,
-                });
-                *step_number += 1;
-            },
-            
-            ASTNode::Boolean { value } => {
-                steps.push(SemanticStep {
-                    step_number: *step_number,
-                    description: format!("Literal booleano: {} (tipo: bool)", value),
-                    node_type: "Boolean".to_string(),
-                    action: "Verificar tipo booleano".to_string(),
-                    symbol_added: None,
-                    type_check: Some("bool".to_string()),
-                    error: None,
-                });
-                *step_number += 1;
-            },
-            
-            ASTNode::Program { statements } => {
-                for stmt in statements {
-                    analyze_node(stmt, steps, symbol_table, type_checks, step_number);
-                }
-            },
-            
-            ASTNode::ExpressionStatement { expression } => {
-                analyze_node(expression, steps, symbol_table, type_checks, step_number);
-            },
-            
-            _ => {
-                steps.push(SemanticStep {
-                    step_number: *step_number,
-                    description: "Analizando nodo".to_string(),
-                    node_type: node.node_type().to_string(),
-                    action: "Procesar nodo".to_string(),
-                    symbol_added: None,
-                    type_check: None,
-                    error: None,
-                });
-                *step_number += 1;
-            }
+    // A chain like `1+1+1+...` is as deep as the program is long, and any thread may
+    // compile, serialize or drop it, so every one gets the stack the largest program needs
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_stack_size(stack_size_for(config.analysis.max_source_bytes))
+        .build()
+        .expect("Failed to start the async runtime")
+        .block_on(serve(config));
+}
+
+async fn serve(config: Config) {
+    let examples = match examples::load() {
+        Ok(files) => files.into_iter().map(|file| file.example).collect(),
+        Err(e) => {
+            eprintln!("Invalid example: {}", e);
+            std::process::exit(1);
         }
-    }
+    };
+    let snippet_store = match snippets::open(&config.snippets) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("Cannot open snippet storage at {}: {}", config.snippets.path.display(), e);
+            std::process::exit(1);
+        }
+    };
+    telemetry::init_logging(&config);
+    let metrics = telemetry::install_metrics();
+    let address = config.bind_address().expect("validated when loading the configuration");
     
-    // Analyze the AST
-    analyze_node(&ast, &mut steps, &mut symbol_table, &mut type_checks, &mut step_number);
+    let allowed_origins = if config.allows_any_origin() {
+        AllowOrigin::from(Any)
+    } else {
+        AllowOrigin::list(config.server.cors_origins.iter().map(|origin| {
+            origin.parse().expect("validated when loading the configuration")
+        }))
+    };
+    let body_limit = config.server.body_limit_bytes;
+    let shutdown_timeout = Duration::from_secs(config.server.shutdown_timeout_secs);
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let state = AppState {
+        config: Arc::new(config),
+        examples: Arc::new(examples),
+        snippets: snippet_store.clone(),
+        started_at: Instant::now(),
+        draining: Arc::new(AtomicBool::new(false)),
+        shutdown: shutdown_rx,
+    };
+    let draining = state.draining.clone();
+    tokio::spawn(snippets::purge_periodically(snippet_store));
     
-    // Final step
-    steps.push(SemanticStep {
-        step_number,
-        description: "Análisis semántico completado".to_string(),
-        node_type: "Program".to_string(),
-        action: "Validación final".to_string(),
-        symbol_added: None,
-        type_check: None,
-        error: None,
-    });
+    let api = Router::new()
+        .route("/compile", post(compile))
+        .route("/live", get(live::live_analysis))
+        .route("/tokenize", post(tokenize))
+        .route("/parse", post(parse))
+        .route("/visualize", post(visualize))
+        .route("/semantic-analysis", post(semantic_analysis))
+        .route("/examples", get(get_examples))
+        .route("/snippets", post(snippets::save_snippet))
+        .route("/snippets/:id", get(snippets::load_snippet));
     
-    Ok(ResponseJson(SemanticAnalysisResponse {
-        steps,
-        symbol_table,
-        type_checks,
-        success: true,
-        error: None,
-    }))
-}
-
-#[tokio::main]
-async fn main() {
+    // The unversioned `/api/...` routes are kept as deprecated aliases of v1
     let app = Router::new()
         .route("/", get(health_check))
-        .route("/api/tokenize", post(tokenize))
-        .route("/api/parse", post(parse))
-        .route("/api/visualize", post(visualize))
-        .route("/api/semantic-analysis", post(semantic_analysis))
-        .route("/api/examples", get(get_examples))
+        .nest("/api/v1", api.clone())
+        .nest("/api", api)
+        .route_layer(middleware::from_fn(telemetry::track_metrics))
+        .route("/api/openapi.json", get(openapi::openapi_json))
+        .route("/metrics", get(move || std::future::ready(metrics.render())))
+        .route("/healthz", get(health::healthz))
+        .route("/readyz", get(health::readyz))
+        .fallback(error::not_found)
+        .with_state(state)
+        .layer(DefaultBodyLimit::max(body_limit))
+        .layer(CatchPanicLayer::custom(error::panic_response))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(telemetry::request_span)
+                        .on_response(DefaultOnResponse::new().level(Level::INFO)),
+                )
+                .layer(PropagateRequestIdLayer::x_request_id()),
+        )
         .layer(
             CorsLayer::new()
-                .allow_origin(Any)
+                .allow_origin(allowed_origins)
                 .allow_methods([Method::GET, Method::POST])
                 .allow_headers(Any),
         );
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
+    let listener = tokio::net::TcpListener::bind(address)
         .await
-        .expect("Failed to bind to port 3000");
+        .unwrap_or_else(|e| panic!("Failed to bind to {}: {}", address, e));
     
-    println!("🚀 Compiler Backend running on http://localhost:3000");
+    tracing::info!(%address, "🚀 Compiler Backend running");
     
-    axum::serve(listener, app)
-        .await
-        .expect("Failed to start server");
-}
\ No newline at end of file
+    // Stop accepting connections on SIGTERM or Ctrl+C, then give in-flight
+    // requests up to the drain timeout to finish
+    let mut drain_started = shutdown_tx.subscribe();
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        tracing::info!(timeout_secs = shutdown_timeout.as_secs(), "shutting down, draining connections");
+        draining.store(true, Ordering::Relaxed);
+        let _ = shutdown_tx.send(true);
+    });
+    let drain_deadline = async move {
+        let _ = drain_started.wait_for(|started| *started).await;
+        tokio::time::sleep(shutdown_timeout).await;
+    };
+    
+    tokio::select! {
+        result = server => {
+            result.expect("Failed to start server");
+            tracing::info!("shutdown complete");
+        },
+        _ = drain_deadline => {
+            tracing::warn!("drain timeout elapsed, dropping remaining connections");
+        },
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
@@ -0,0 +1,56 @@
+//! OpenAPI description of the HTTP API, served at `/api/openapi.json`.
+//!
+//! Schemas come from the Rust types themselves (including the compiler's
+//! `ASTNode`), so clients can generate their types from the document instead
+//! of copying the structs by hand.
+
+use axum::response::Json;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Compiler Visualizer API",
+        description = "Lexing, parsing, AST visualization and semantic analysis of Go-like programs.",
+    ),
+    paths(
+        crate::compile,
+        crate::tokenize,
+        crate::parse,
+        crate::visualize,
+        crate::semantic_analysis,
+        crate::get_examples,
+        crate::snippets::save_snippet,
+        crate::snippets::load_snippet,
+        crate::live::live_analysis,
+        crate::health::healthz,
+        crate::health::readyz,
+    ),
+    tags(
+        (name = "compiler", description = "Compiler pipeline stages"),
+        (name = "snippets", description = "Shareable programs"),
+        (name = "health", description = "Liveness and readiness probes"),
+    )
+)]
+pub struct ApiDoc;
+
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_document_describes_versioned_routes_and_ast() {
+        let document = ApiDoc::openapi();
+        assert!(document.paths.paths.contains_key("/api/v1/compile"));
+        assert!(document.paths.paths.keys().all(|path| !path.starts_with("/api/") || path.starts_with("/api/v1/")));
+        
+        let schemas = document.components.expect("schemas are collected").schemas;
+        let ast = serde_json::to_value(&schemas["ASTNode"]).unwrap();
+        let variants = ast["oneOf"].as_array().expect("ASTNode is a tagged union");
+        assert!(variants.iter().any(|variant| variant["required"][0] == "BinaryOp"));
+    }
+}
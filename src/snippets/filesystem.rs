@@ -0,0 +1,89 @@
+//! Snippets as `<id>.json` files in one directory.
+
+use super::{Snippet, SnippetStore, StoreError};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub struct FilesystemStore {
+    dir: PathBuf,
+    /// Serializes saves, so two requests sharing the same program cannot race on its file
+    writing: Mutex<()>,
+}
+
+impl FilesystemStore {
+    /// Uses `dir`, creating it if needed
+    pub fn open(dir: &Path) -> Result<Self, StoreError> {
+        fs::create_dir_all(dir)?;
+        Ok(FilesystemStore {
+            dir: dir.to_path_buf(),
+            writing: Mutex::new(()),
+        })
+    }
+    
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+    
+    fn read(&self, id: &str) -> Result<Option<Snippet>, StoreError> {
+        let bytes = match fs::read(self.path(id)) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|source| StoreError::Corrupt { id: id.to_string(), source })
+    }
+    
+    /// Writes to a temporary file first, so readers never see half a snippet
+    fn write(&self, snippet: &Snippet) -> Result<(), StoreError> {
+        let temporary = self.dir.join(format!(".{}.tmp", snippet.id));
+        let json = serde_json::to_vec(snippet).expect("snippets serialize to JSON");
+        fs::write(&temporary, json)?;
+        fs::rename(&temporary, self.path(&snippet.id))?;
+        Ok(())
+    }
+}
+
+impl SnippetStore for FilesystemStore {
+    fn save(&self, snippet: Snippet) -> Result<Snippet, StoreError> {
+        let _writing = self.writing.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let stored = match self.read(&snippet.id)? {
+            Some(existing) if existing.code != snippet.code => return Err(StoreError::IdCollision { id: snippet.id }),
+            Some(existing) => Snippet { expires_at: snippet.expires_at, ..existing },
+            None => snippet,
+        };
+        self.write(&stored)?;
+        Ok(stored)
+    }
+    
+    fn load(&self, id: &str, now: u64) -> Result<Option<Snippet>, StoreError> {
+        Ok(self.read(id)?.filter(|snippet| !snippet.is_expired(now)))
+    }
+    
+    fn purge_expired(&self, now: u64) -> Result<usize, StoreError> {
+        let _writing = self.writing.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut purged = 0;
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let Some(id) = path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+            else {
+                continue;
+            };
+            match self.read(id) {
+                Ok(Some(snippet)) if snippet.is_expired(now) => {
+                    fs::remove_file(&path)?;
+                    purged += 1;
+                },
+                Ok(_) => {},
+                // One bad file should not stop the others from expiring
+                Err(e) => tracing::warn!(error = %e, "skipping unreadable snippet"),
+            }
+        }
+        Ok(purged)
+    }
+}
@@ -0,0 +1,315 @@
+//! Shareable programs.
+//!
+//! `POST /api/v1/snippets` stores a program and returns its ID, the first 12
+//! hex digits of the program's SHA-256, so sharing the same program twice
+//! gives the same link. If another program already has that ID, the ID takes
+//! more digits of the hash until it is free. `GET /api/v1/snippets/{id}` returns it until it
+//! expires; each save pushes the expiry back by `snippets.expiry_days`.
+//!
+//! Storage goes through [`SnippetStore`], implemented over a directory of JSON
+//! files and over SQLite; `snippets.backend` picks one.
+
+mod filesystem;
+mod sqlite;
+
+pub use filesystem::FilesystemStore;
+pub use sqlite::SqliteStore;
+
+use crate::config::{SnippetBackend, SnippetsConfig};
+use crate::error::{ApiError, ApiJson, ErrorBody, ErrorEnvelope};
+use crate::{telemetry, AppState};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+const ID_LENGTH: usize = 12;
+/// Digits an ID grows by when a shorter one belongs to another program
+const ID_LENGTH_STEP: usize = 4;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+/// How often expired snippets are deleted
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Snippet {
+    pub id: String,
+    pub code: String,
+    /// Unix time in seconds of the first save
+    pub created_at: u64,
+    /// Unix time in seconds when the snippet is deleted; `null` if it never is
+    pub expires_at: Option<u64>,
+}
+
+impl Snippet {
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("snippet storage I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    
+    #[error("snippet database failed: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    
+    #[error("stored snippet {id} is corrupt: {source}")]
+    Corrupt { id: String, source: serde_json::Error },
+    
+    #[error("snippet ID {id} already belongs to another program")]
+    IdCollision { id: String },
+}
+
+/// Storage backend for snippets. Calls block, so handlers run them on the
+/// blocking thread pool.
+pub trait SnippetStore: Send + Sync {
+    /// Stores `snippet`. If the same program is already stored under its ID,
+    /// only its expiry is updated; if another program is, nothing changes and
+    /// the save fails with `StoreError::IdCollision`. Returns the snippet as stored.
+    fn save(&self, snippet: Snippet) -> Result<Snippet, StoreError>;
+    
+    /// The snippet with `id`, unless there is none or it expired before `now`
+    fn load(&self, id: &str, now: u64) -> Result<Option<Snippet>, StoreError>;
+    
+    /// Deletes the snippets expired before `now` and returns how many there were
+    fn purge_expired(&self, now: u64) -> Result<usize, StoreError>;
+}
+
+/// Opens the backend selected by the configuration
+pub fn open(config: &SnippetsConfig) -> Result<Arc<dyn SnippetStore>, StoreError> {
+    Ok(match config.backend {
+        SnippetBackend::Filesystem => Arc::new(FilesystemStore::open(&config.path)?),
+        SnippetBackend::Sqlite => Arc::new(SqliteStore::open(&config.path)?),
+    })
+}
+
+/// The program's SHA-256 in hex
+fn content_hash(code: &str) -> String {
+    Sha256::digest(code.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// ID of a program: the start of its SHA-256 in hex
+pub fn snippet_id(code: &str) -> String {
+    content_hash(code)[..ID_LENGTH].to_string()
+}
+
+/// Whether `id` could have come from `save_unique`; also keeps IDs safe to use as file names
+fn is_valid_id(id: &str) -> bool {
+    (ID_LENGTH..=Sha256::output_size() * 2).contains(&id.len())
+        && id.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Saves a program under the shortest prefix of its hash, starting at
+/// `ID_LENGTH` digits, that no other program holds
+fn save_unique(store: &dyn SnippetStore, code: String, created_at: u64, expires_at: Option<u64>) -> Result<Snippet, StoreError> {
+    let hash = content_hash(&code);
+    let mut id = snippet_id(&code);
+    loop {
+        let snippet = Snippet { id, code: code.clone(), created_at, expires_at };
+        match store.save(snippet) {
+            Err(StoreError::IdCollision { id: taken }) if taken.len() < hash.len() => {
+                tracing::warn!(id = %taken, "snippet ID collision, using a longer ID");
+                id = hash[..(taken.len() + ID_LENGTH_STEP).min(hash.len())].to_string();
+            },
+            result => return result,
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SaveSnippetRequest {
+    code: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SaveSnippetResponse {
+    id: String,
+    /// Unix time in seconds when the snippet is deleted; `null` if it never is
+    expires_at: Option<u64>,
+}
+
+fn not_found(id: &str) -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND, ErrorBody::new("snippet_not_found", format!("no snippet with ID {:?}", id)))
+}
+
+/// Runs a store call on the blocking thread pool
+async fn with_store<T: Send + 'static>(
+    store: &Arc<dyn SnippetStore>,
+    call: impl FnOnce(&dyn SnippetStore) -> Result<T, StoreError> + Send + 'static,
+) -> Result<T, ApiError> {
+    let store = store.clone();
+    match tokio::task::spawn_blocking(move || call(store.as_ref())).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => {
+            tracing::error!(error = %e, "snippet store failed");
+            Err(ApiError::internal())
+        },
+        Err(e) => {
+            tracing::error!(error = %e, "snippet store task failed");
+            Err(ApiError::internal())
+        },
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/snippets",
+    tag = "snippets",
+    request_body = SaveSnippetRequest,
+    responses(
+        (status = 201, description = "Program stored; the same program always gets the same ID", body = SaveSnippetResponse),
+        (status = 400, description = "Body is not valid JSON", body = ErrorEnvelope),
+        (status = 413, description = "Program larger than `snippets.max_bytes`", body = ErrorEnvelope),
+        (status = 422, description = "Empty program", body = ErrorEnvelope),
+    )
+)]
+pub async fn save_snippet(
+    State(state): State<AppState>,
+    ApiJson(request): ApiJson<SaveSnippetRequest>,
+) -> Result<(StatusCode, Json<SaveSnippetResponse>), ApiError> {
+    telemetry::record_source_size("snippets", request.code.len());
+    let limit = state.config.snippets.max_bytes;
+    if request.code.len() > limit {
+        return Err(ApiError::source_too_large(request.code.len(), limit));
+    }
+    if request.code.trim().is_empty() {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorBody::new("empty_snippet", "cannot share an empty program"),
+        ));
+    }
+    
+    let now = unix_now();
+    let expiry_days = state.config.snippets.expiry_days;
+    let expires_at = (expiry_days > 0).then(|| now.saturating_add(expiry_days.saturating_mul(SECONDS_PER_DAY)));
+    let stored = with_store(&state.snippets, move |store| save_unique(store, request.code, now, expires_at)).await?;
+    tracing::info!(id = %stored.id, "snippet saved");
+    
+    Ok((StatusCode::CREATED, Json(SaveSnippetResponse {
+        id: stored.id,
+        expires_at: stored.expires_at,
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/snippets/{id}",
+    tag = "snippets",
+    params(("id" = String, Path, description = "ID returned when the program was saved")),
+    responses(
+        (status = 200, description = "The stored program", body = Snippet),
+        (status = 404, description = "No such snippet, or it expired", body = ErrorEnvelope),
+    )
+)]
+pub async fn load_snippet(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<Snippet>, ApiError> {
+    if !is_valid_id(&id) {
+        return Err(not_found(&id));
+    }
+    let lookup = id.clone();
+    match with_store(&state.snippets, move |store| store.load(&lookup, unix_now())).await? {
+        Some(snippet) => Ok(Json(snippet)),
+        None => Err(not_found(&id)),
+    }
+}
+
+/// Deletes expired snippets every `PURGE_INTERVAL`, starting now
+pub async fn purge_periodically(store: Arc<dyn SnippetStore>) {
+    let mut interval = tokio::time::interval(PURGE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let store = store.clone();
+        match tokio::task::spawn_blocking(move || store.purge_expired(unix_now())).await {
+            Ok(Ok(0)) => {},
+            Ok(Ok(purged)) => tracing::info!(purged, "expired snippets deleted"),
+            Ok(Err(e)) => tracing::warn!(error = %e, "cannot delete expired snippets"),
+            Err(e) => tracing::warn!(error = %e, "snippet purge task failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn snippet(code: &str, created_at: u64, expires_at: Option<u64>) -> Snippet {
+        Snippet { id: snippet_id(code), code: code.to_string(), created_at, expires_at }
+    }
+    
+    /// Behaviour every backend must share
+    fn check_store(store: &dyn SnippetStore) {
+        let first = store.save(snippet("x = 1", 100, Some(200))).unwrap();
+        assert_eq!(first, snippet("x = 1", 100, Some(200)));
+        assert_eq!(store.load(&first.id, 150).unwrap(), Some(first.clone()));
+        assert_eq!(store.load(&snippet_id("y = 2"), 150).unwrap(), None);
+        
+        // Saving the same program again only pushes the expiry back
+        let again = store.save(snippet("x = 1", 180, Some(300))).unwrap();
+        assert_eq!(again, snippet("x = 1", 100, Some(300)));
+        assert_eq!(store.load(&first.id, 250).unwrap(), Some(again));
+        assert_eq!(store.load(&first.id, 300).unwrap(), None);
+        
+        store.save(snippet("kept = 1", 100, None)).unwrap();
+        store.save(snippet("old = 1", 100, Some(150))).unwrap();
+        assert_eq!(store.purge_expired(400).unwrap(), 2);
+        assert!(store.load(&snippet_id("kept = 1"), u64::MAX / 2).unwrap().is_some());
+        assert_eq!(store.load(&snippet_id("old = 1"), 0).unwrap(), None);
+        
+        // Another program under a taken ID is refused and leaves the stored one alone
+        let taken = store.save(snippet("a = 1", 100, None)).unwrap();
+        let impostor = Snippet { code: "b = 2".to_string(), ..snippet("a = 1", 200, Some(300)) };
+        assert!(matches!(store.save(impostor), Err(StoreError::IdCollision { id }) if id == taken.id));
+        assert_eq!(store.load(&taken.id, 250).unwrap(), Some(taken.clone()));
+        
+        // ...so the colliding program gets a longer ID, and keeps it when saved again
+        let planted = Snippet { code: "a = 1".to_string(), ..snippet("b = 2", 100, None) };
+        store.save(planted.clone()).unwrap();
+        let moved = save_unique(store, "b = 2".to_string(), 100, None).unwrap();
+        assert_eq!(moved.id, content_hash("b = 2")[..ID_LENGTH + ID_LENGTH_STEP]);
+        assert_eq!(moved.code, "b = 2");
+        assert_eq!(save_unique(store, "b = 2".to_string(), 150, None).unwrap().id, moved.id);
+        assert_eq!(store.load(&planted.id, 150).unwrap(), Some(planted));
+    }
+    
+    #[test]
+    fn test_filesystem_store() {
+        let dir = std::env::temp_dir().join(format!("compiler-snippets-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        check_store(&FilesystemStore::open(&dir).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[test]
+    fn test_sqlite_store() {
+        check_store(&SqliteStore::open(std::path::Path::new(":memory:")).unwrap());
+    }
+    
+    #[test]
+    fn test_ids_are_short_content_hashes() {
+        let id = snippet_id("x = 1");
+        assert_eq!(id, snippet_id("x = 1"));
+        assert_ne!(id, snippet_id("x = 2"));
+        assert!(is_valid_id(&id));
+        assert!(is_valid_id(&content_hash("x = 1")));
+        for bad in ["", "../../etc/passwd", "ABCDEF012345", "0123456789a", &"0".repeat(65)] {
+            assert!(!is_valid_id(bad), "{:?}", bad);
+        }
+    }
+}
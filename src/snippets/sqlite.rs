@@ -0,0 +1,82 @@
+//! Snippets in a table of a SQLite database.
+
+use super::{Snippet, SnippetStore, StoreError};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS snippets (
+        id TEXT PRIMARY KEY,
+        code TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        expires_at INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS snippets_expires_at ON snippets (expires_at);
+";
+
+pub struct SqliteStore {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Opens (or creates) the database at `path`; `:memory:` gives a private in-memory one
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(SqliteStore { connection: Mutex::new(connection) })
+    }
+    
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn snippet_from_row(row: &Row) -> rusqlite::Result<Snippet> {
+    Ok(Snippet {
+        id: row.get(0)?,
+        code: row.get(1)?,
+        created_at: row.get(2)?,
+        expires_at: row.get(3)?,
+    })
+}
+
+impl SnippetStore for SqliteStore {
+    fn save(&self, snippet: Snippet) -> Result<Snippet, StoreError> {
+        let connection = self.connection();
+        // A row under the ID holding another program is left as is, and nothing is returned
+        let stored = connection
+            .query_row(
+                "INSERT INTO snippets (id, code, created_at, expires_at) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (id) DO UPDATE SET expires_at = excluded.expires_at WHERE code = excluded.code
+                 RETURNING id, code, created_at, expires_at",
+                params![snippet.id, snippet.code, snippet.created_at, snippet.expires_at],
+                snippet_from_row,
+            )
+            .optional()?;
+        stored.ok_or(StoreError::IdCollision { id: snippet.id })
+    }
+    
+    fn load(&self, id: &str, now: u64) -> Result<Option<Snippet>, StoreError> {
+        let snippet = self.connection()
+            .query_row(
+                "SELECT id, code, created_at, expires_at FROM snippets
+                 WHERE id = ?1 AND (expires_at IS NULL OR expires_at > ?2)",
+                params![id, now],
+                snippet_from_row,
+            )
+            .optional()?;
+        Ok(snippet)
+    }
+    
+    fn purge_expired(&self, now: u64) -> Result<usize, StoreError> {
+        let purged = self.connection().execute(
+            "DELETE FROM snippets WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+            params![now],
+        )?;
+        Ok(purged)
+    }
+}
@@ -0,0 +1,95 @@
+//! Logging, request tracing and Prometheus metrics.
+//!
+//! Metrics exposed on `/metrics`:
+//!
+//! - `http_requests_total{endpoint, method, status}`
+//! - `http_request_duration_seconds{endpoint, method}`
+//! - `compiler_stage_duration_seconds{stage}`
+//! - `compiler_errors_total{kind}`
+//! - `compiler_source_bytes{endpoint}`
+
+use crate::config::{Config, LogFormat};
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::time::Instant;
+use tracing_subscriber::EnvFilter;
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+const DURATION_BUCKETS: &[f64] = &[
+    0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+const SIZE_BUCKETS: &[f64] = &[
+    16.0, 64.0, 256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0,
+];
+
+/// Installs the global log subscriber at the configured level and format
+pub fn init_logging(config: &Config) {
+    let filter = EnvFilter::new(&config.log_level);
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match config.log_format {
+        LogFormat::Text => builder.init(),
+        LogFormat::Json => builder.json().flatten_event(true).with_current_span(true).init(),
+    }
+}
+
+/// Installs the global metrics recorder; the handle renders `/metrics`
+pub fn install_metrics() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), DURATION_BUCKETS)
+        .and_then(|builder| builder.set_buckets_for_metric(Matcher::Suffix("_bytes".to_string()), SIZE_BUCKETS))
+        .expect("bucket lists are not empty")
+        .install_recorder()
+        .expect("metrics recorder is installed once")
+}
+
+/// Span for one HTTP request, tagged with the ID set by `SetRequestIdLayer`
+pub fn request_span(request: &Request<Body>) -> tracing::Span {
+    let request_id = request.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|id| id.to_str().ok())
+        .unwrap_or("-");
+    tracing::info_span!(
+        "request",
+        request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    )
+}
+
+/// Counts requests and records their latency per matched route
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let endpoint = request.extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+    let start = Instant::now();
+    
+    let response = next.run(request).await;
+    
+    let status = response.status().as_u16().to_string();
+    metrics::counter!("http_requests_total", "endpoint" => endpoint.clone(), "method" => method.clone(), "status" => status)
+        .increment(1);
+    metrics::histogram!("http_request_duration_seconds", "endpoint" => endpoint, "method" => method)
+        .record(start.elapsed().as_secs_f64());
+    response
+}
+
+pub fn record_stage(stage: &'static str, seconds: f64) {
+    metrics::histogram!("compiler_stage_duration_seconds", "stage" => stage).record(seconds);
+}
+
+pub fn record_error(kind: &'static str) {
+    tracing::debug!(kind, "compiler error");
+    metrics::counter!("compiler_errors_total", "kind" => kind).increment(1);
+}
+
+pub fn record_source_size(endpoint: &'static str, bytes: usize) {
+    metrics::histogram!("compiler_source_bytes", "endpoint" => endpoint).record(bytes as f64);
+}